@@ -0,0 +1,79 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use sha2::{Digest, Sha256};
+
+/// The files under `path`, recursively, sorted.
+fn files(path: &Path) -> Vec<PathBuf> {
+    if path.is_file() {
+        return vec![path.to_path_buf()];
+    }
+
+    let mut files = fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .flat_map(|e| files(&e.unwrap().path()))
+                .collect::<Vec<PathBuf>>()
+        })
+        .unwrap_or_default();
+    files.sort();
+
+    files
+}
+
+/// Hashes the names and contents of the files under `paths`, relative to `root`.
+fn hash(root: &Path, paths: &[PathBuf], hasher: &mut Sha256) {
+    for path in paths {
+        println!("cargo:rerun-if-changed={}", path.display());
+
+        for file in files(path) {
+            hasher.update(
+                file.strip_prefix(root)
+                    .unwrap()
+                    .to_str()
+                    .unwrap()
+                    .as_bytes(),
+            );
+            hasher.update(fs::read(&file).unwrap());
+        }
+    }
+}
+
+/// Writes the version of each day's solver, a hash of the sources it's built from: the shared
+/// `aoc_2020` library and the day's crate. Cached answers are only reused for the same version.
+fn main() {
+    let root = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap())
+        .parent()
+        .unwrap()
+        .to_path_buf();
+
+    let mut shared = Sha256::new();
+    hash(
+        &root,
+        &[root.join("Cargo.toml"), root.join("src")],
+        &mut shared,
+    );
+
+    let mut code = String::from("pub static SOLVER_VERSIONS: [&str; 25] = [\n");
+    for day in 1..=25 {
+        let krate = root.join(format!("d{:02}", day));
+        let mut hasher = shared.clone();
+        hash(
+            &root,
+            &[krate.join("Cargo.toml"), krate.join("src")],
+            &mut hasher,
+        );
+
+        let version = hasher
+            .finalize()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect::<String>();
+        code.push_str(&format!("    {:?},\n", version));
+    }
+    code.push_str("];\n");
+
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("solver_versions.rs");
+    fs::write(out, code).unwrap();
+}
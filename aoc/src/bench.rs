@@ -0,0 +1,59 @@
+//! Side-by-side timings of the implementations of a day.
+
+use std::time::{Duration, Instant};
+
+use aoc_2020::Diagnostic;
+
+use crate::days::Day;
+
+/// Median duration of `runs` calls of `f`.
+fn median<T>(runs: usize, f: impl Fn() -> Result<T, Diagnostic>) -> Result<Duration, Diagnostic> {
+    let mut durations = Vec::with_capacity(runs);
+
+    for _ in 0..runs.max(1) {
+        let start = Instant::now();
+        f()?;
+        durations.push(start.elapsed());
+    }
+    durations.sort();
+
+    Ok(durations[durations.len() / 2])
+}
+
+fn format_duration(d: Duration) -> String {
+    format!("{:.3}ms", d.as_secs_f64() * 1000.0)
+}
+
+/// Times every implementation of each part, with its speedup relative to the default one.
+///
+/// Days without alternative implementations are timed as a whole.
+pub fn bench(day: &Day, input: &[String], runs: usize) -> Result<(), Diagnostic> {
+    if day.implementations.is_empty() {
+        let d = median(runs, || (day.solve)(input))?;
+        println!("  {:<12}{:>12}", "solve", format_duration(d));
+
+        return Ok(());
+    }
+
+    for part in 1..=2 {
+        println!("Part {}", part);
+
+        let mut baseline = None;
+        for implementation in day.implementations_of(part) {
+            let d = median(runs, || (implementation.solve)(input))?;
+            let line = format!("  {:<12}{:>12}", implementation.name, format_duration(d));
+
+            match baseline {
+                None => {
+                    println!("{}", line);
+                    baseline = Some(d);
+                }
+                Some(baseline) => {
+                    println!("{}  {:.2}x", line, baseline.as_secs_f64() / d.as_secs_f64())
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,129 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Answers of a day for a given input and solver version.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Entry {
+    pub day: u8,
+    pub input_hash: String,
+    pub solver_version: String,
+    pub part1: String,
+    pub part2: String,
+    pub duration_us: u64,
+}
+
+/// On-disk cache of answers, one JSON file per entry.
+pub struct Cache {
+    dir: PathBuf,
+}
+
+impl Cache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Cache { dir: dir.into() }
+    }
+
+    fn path(&self, day: u8, input_hash: &str, solver_version: &str) -> PathBuf {
+        self.dir.join(format!(
+            "d{:02}-{}-{}.json",
+            day,
+            &input_hash[..16],
+            &solver_version[..16]
+        ))
+    }
+
+    pub fn get(&self, day: u8, input_hash: &str, solver_version: &str) -> Option<Entry> {
+        let data = fs::read(self.path(day, input_hash, solver_version)).ok()?;
+        let entry: Entry = serde_json::from_slice(&data).ok()?;
+
+        if entry.day == day
+            && entry.input_hash == input_hash
+            && entry.solver_version == solver_version
+        {
+            Some(entry)
+        } else {
+            None
+        }
+    }
+
+    pub fn put(&self, entry: &Entry) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+
+        let data = serde_json::to_vec_pretty(entry)?;
+        fs::write(
+            self.path(entry.day, &entry.input_hash, &entry.solver_version),
+            data,
+        )
+    }
+}
+
+/// Hex-encoded SHA-256 of `data`.
+pub fn hash(data: &[u8]) -> String {
+    Sha256::digest(data)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Hash of an input, independent of how it was split into lines.
+pub fn hash_input(input: &[String]) -> String {
+    hash(input.join("\n").as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+
+    use crate::cache::{hash, hash_input, Cache, Entry};
+
+    #[test]
+    fn test_hash() {
+        assert_eq!(
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+            hash(b"abc")
+        );
+        assert_eq!(
+            hash_input(&[String::from("0,3,6")]),
+            hash_input(&[String::from("0,3,6")])
+        );
+        assert_ne!(
+            hash_input(&[String::from("0,3,6")]),
+            hash_input(&[String::from("0,3,7")])
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let dir = env::temp_dir().join(format!("aoc-cache-test-{}", std::process::id()));
+        let cache = Cache::new(&dir);
+
+        let entry = Entry {
+            day: 15,
+            input_hash: hash(b"0,3,6"),
+            solver_version: hash(b"v1"),
+            part1: String::from("436"),
+            part2: String::from("175594"),
+            duration_us: 4_501_470,
+        };
+
+        assert_eq!(
+            None,
+            cache.get(15, &entry.input_hash, &entry.solver_version)
+        );
+
+        cache.put(&entry).unwrap();
+
+        assert_eq!(
+            Some(entry.clone()),
+            cache.get(15, &entry.input_hash, &entry.solver_version)
+        );
+        assert_eq!(None, cache.get(15, &entry.input_hash, &hash(b"v2")));
+        assert_eq!(None, cache.get(15, &hash(b"0,3,7"), &entry.solver_version));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,152 @@
+use aoc_2020::config;
+use aoc_2020::explain::Step;
+use aoc_2020::gen::Generator;
+use aoc_2020::{Diagnostic, Implementation};
+
+include!(concat!(env!("OUT_DIR"), "/solver_versions.rs"));
+
+/// Solves both parts of a day, returning the answers as text.
+pub type Solver = fn(&[String]) -> Result<(String, String), Diagnostic>;
+
+/// Returns the intermediate steps of a day's solution.
+pub type Explainer = fn(&[String]) -> Result<Vec<Step>, Diagnostic>;
+
+/// Renders the state of a part's solution after each step, one text grid per step.
+#[cfg(feature = "tui")]
+pub type Visualizer = fn(&[String], u8) -> Result<Vec<Vec<String>>, Diagnostic>;
+
+/// A day of the calendar and its solver.
+pub struct Day {
+    pub number: u8,
+    pub implemented: bool,
+    pub solve: Solver,
+    /// Hash of the sources the solver is built from, to detect when cached answers are stale.
+    pub version: &'static str,
+    /// Random input generator, for the days that have one.
+    pub generate: Option<Generator>,
+    /// Slow but straightforward solver to check `solve` against.
+    pub reference: Option<Solver>,
+    /// Named implementations of each part, for the days that have alternatives.
+    pub implementations: &'static [Implementation],
+    /// Explanation of the solution, for the days that have one.
+    pub explain: Option<Explainer>,
+    /// Visualization of the solution, for the grid days that have one.
+    #[cfg(feature = "tui")]
+    pub visualize: Option<Visualizer>,
+}
+
+impl Day {
+    /// Name of the input file of the day, e.g. `d01.txt`.
+    pub fn input_name(&self) -> String {
+        format!("d{:02}.txt", self.number)
+    }
+
+    /// Path of the input file of the day in the configured input directory.
+    pub fn input_path(&self) -> String {
+        format!("{}/{}", config::get().input_dir, self.input_name())
+    }
+
+    /// Names of the implementations, in order of registration.
+    pub fn implementation_names(&self) -> Vec<&'static str> {
+        let mut names = Vec::new();
+        for i in self.implementations {
+            if !names.contains(&i.name) {
+                names.push(i.name);
+            }
+        }
+
+        names
+    }
+
+    /// Implementations of `part`, the default one first.
+    pub fn implementations_of(&self, part: u8) -> impl Iterator<Item = &Implementation> {
+        self.implementations.iter().filter(move |i| i.part == part)
+    }
+
+    /// Solves both parts with the implementation `name`, falling back to the default
+    /// implementation for a part that doesn't have one by that name.
+    pub fn solve_with(&self, name: &str, input: &[String]) -> Result<(String, String), Diagnostic> {
+        let solve_part = |part| {
+            let mut implementations = self.implementations_of(part);
+            let default = implementations.next().unwrap();
+            let implementation = implementations.find(|i| i.name == name).unwrap_or(default);
+
+            (implementation.solve)(input)
+        };
+
+        Ok((solve_part(1)?, solve_part(2)?))
+    }
+}
+
+/// Declares a day, followed by the optional parts its crate provides: `gen` for a `gen` module,
+/// `reference` for a `reference` module, `impls` for `IMPLEMENTATIONS`, `explain` for an
+/// `explain` function and `frames` for a `frames` function.
+macro_rules! day {
+    (@gen $krate:ident) => { None };
+    (@gen $krate:ident gen $($rest:ident)*) => { Some($krate::gen::generate) };
+    (@gen $krate:ident $other:ident $($rest:ident)*) => { day!(@gen $krate $($rest)*) };
+    (@reference $krate:ident) => { None };
+    (@reference $krate:ident reference $($rest:ident)*) => {
+        Some(|input| {
+            $krate::reference::solve(input).map(|(p1, p2)| (p1.to_string(), p2.to_string()))
+        })
+    };
+    (@reference $krate:ident $other:ident $($rest:ident)*) => {
+        day!(@reference $krate $($rest)*)
+    };
+    (@impls $krate:ident) => { &[] };
+    (@impls $krate:ident impls $($rest:ident)*) => { $krate::IMPLEMENTATIONS };
+    (@impls $krate:ident $other:ident $($rest:ident)*) => { day!(@impls $krate $($rest)*) };
+    (@explain $krate:ident) => { None };
+    (@explain $krate:ident explain $($rest:ident)*) => { Some($krate::explain) };
+    (@explain $krate:ident $other:ident $($rest:ident)*) => { day!(@explain $krate $($rest)*) };
+    (@frames $krate:ident) => { None };
+    (@frames $krate:ident frames $($rest:ident)*) => { Some($krate::frames) };
+    (@frames $krate:ident $other:ident $($rest:ident)*) => { day!(@frames $krate $($rest)*) };
+    ($number:expr, $krate:ident, $implemented:expr $(, $extra:ident)*) => {
+        Day {
+            number: $number,
+            implemented: $implemented,
+            solve: |input| $krate::solve(input).map(|(p1, p2)| (p1.to_string(), p2.to_string())),
+            version: SOLVER_VERSIONS[$number - 1],
+            generate: day!(@gen $krate $($extra)*),
+            reference: day!(@reference $krate $($extra)*),
+            implementations: day!(@impls $krate $($extra)*),
+            explain: day!(@explain $krate $($extra)*),
+            #[cfg(feature = "tui")]
+            visualize: day!(@frames $krate $($extra)*),
+        }
+    };
+}
+
+pub static DAYS: [Day; 25] = [
+    day!(1, d01, true, explain),
+    day!(2, d02, true, explain),
+    day!(3, d03, true, explain),
+    day!(4, d04, true, explain),
+    day!(5, d05, true, explain),
+    day!(6, d06, true, explain),
+    day!(7, d07, true, gen, explain),
+    day!(8, d08, true, explain),
+    day!(9, d09, true, gen, explain),
+    day!(10, d10, true, gen, reference, explain),
+    day!(11, d11, true, gen, impls, frames, explain),
+    day!(12, d12, true, explain),
+    day!(13, d13, true, gen, reference, explain),
+    day!(14, d14, true, gen, reference, explain),
+    day!(15, d15, true, impls, explain),
+    day!(16, d16, true, gen, explain),
+    day!(17, d17, false),
+    day!(18, d18, false),
+    day!(19, d19, false),
+    day!(20, d20, false),
+    day!(21, d21, false),
+    day!(22, d22, false),
+    day!(23, d23, false),
+    day!(24, d24, false),
+    day!(25, d25, false),
+];
+
+pub fn get(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.number == number)
+}
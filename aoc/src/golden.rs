@@ -0,0 +1,119 @@
+//! Known good answers, stored next to each input as `dNN.answers` or `dNN/<name>.answers`, in
+//! the format printed by `aoc run`:
+//!
+//! ```text
+//! Part 1: 436
+//! Part 2: 175594
+//! ```
+
+use std::fs;
+use std::path::Path;
+
+/// Golden answers of a day, a part may be missing when it isn't solved yet.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Golden {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+/// A line of the comparison of answers with the golden ones.
+#[cfg(feature = "tui")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Line {
+    Same(String),
+    Expected(String),
+    Got(String),
+}
+
+impl Golden {
+    pub fn parse(text: &str) -> Self {
+        let mut golden = Golden::default();
+        for line in text.lines() {
+            if let Some(answer) = line.strip_prefix("Part 1: ") {
+                golden.part1 = Some(answer.trim().to_owned());
+            } else if let Some(answer) = line.strip_prefix("Part 2: ") {
+                golden.part2 = Some(answer.trim().to_owned());
+            }
+        }
+
+        golden
+    }
+
+    /// Reads the golden answers at `path`, if there are any.
+    pub fn load(path: impl AsRef<Path>) -> Option<Self> {
+        let text = fs::read_to_string(path).ok()?;
+
+        Some(Golden::parse(&text))
+    }
+
+    /// Whether the answers match the golden ones, ignoring missing parts.
+    #[cfg(feature = "tui")]
+    pub fn matches(&self, part1: &str, part2: &str) -> bool {
+        self.mismatches(part1, part2).is_empty()
+    }
+
+    /// The parts whose answer differs from the golden one, with the golden answer.
+    pub fn mismatches(&self, part1: &str, part2: &str) -> Vec<(u8, &str)> {
+        [(1, &self.part1, part1), (2, &self.part2, part2)]
+            .iter()
+            .filter_map(|&(part, expected, got)| match expected {
+                Some(expected) if expected != got => Some((part, expected.as_str())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Compares the answers with the golden ones, line by line.
+    #[cfg(feature = "tui")]
+    pub fn diff(&self, part1: &str, part2: &str) -> Vec<Line> {
+        let mut lines = Vec::new();
+        for (part, expected, got) in [(1, &self.part1, part1), (2, &self.part2, part2)] {
+            match expected {
+                Some(expected) if expected != got => {
+                    lines.push(Line::Expected(format!("Part {}: {}", part, expected)));
+                    lines.push(Line::Got(format!("Part {}: {}", part, got)));
+                }
+                _ => lines.push(Line::Same(format!("Part {}: {}", part, got))),
+            }
+        }
+
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::golden::Golden;
+    #[cfg(feature = "tui")]
+    use crate::golden::Line;
+
+    #[test]
+    fn test_parse() {
+        let golden = Golden::parse("Part 1: 436\nPart 2: 175594\n");
+        assert_eq!(Some("436"), golden.part1.as_deref());
+        assert_eq!(Some("175594"), golden.part2.as_deref());
+
+        let golden = Golden::parse("Part 1: 436\n");
+        assert_eq!(None, golden.part2);
+        assert_eq!(vec![(1, "436")], golden.mismatches("1", "1"));
+    }
+
+    #[test]
+    #[cfg(feature = "tui")]
+    fn test_diff() {
+        let golden = Golden::parse("Part 1: 436\n");
+        assert!(golden.matches("436", "1"));
+        assert!(!golden.matches("1", "1"));
+
+        let golden = Golden::parse("Part 1: 436\nPart 2: 175594\n");
+
+        assert_eq!(
+            vec![
+                Line::Same(String::from("Part 1: 436")),
+                Line::Expected(String::from("Part 2: 175594")),
+                Line::Got(String::from("Part 2: 175593")),
+            ],
+            golden.diff("436", "175593")
+        );
+    }
+}
@@ -0,0 +1,144 @@
+//! The puzzle inputs of a day.
+//!
+//! A day has its historical input, `dNN.txt`, and any number of named ones,
+//! `dNN/<name>.txt`, each of them optionally encrypted and optionally followed by its expected
+//! answers in `dNN.answers` or `dNN/<name>.answers`.
+
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::Path;
+
+use aoc_2020::crypt::ENCRYPTED_EXT;
+use aoc_2020::read_input;
+
+use crate::golden::Golden;
+
+/// Name of the input at `dNN.txt`.
+pub const DEFAULT_NAME: &str = "default";
+
+/// A puzzle input, with the answers expected for it when they are known.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Input {
+    pub name: String,
+    /// Path of the plain input, which may only exist encrypted.
+    pub path: String,
+    pub expected: Option<Golden>,
+}
+
+fn exists(path: &str) -> bool {
+    Path::new(path).is_file() || Path::new(&format!("{}.{}", path, ENCRYPTED_EXT)).is_file()
+}
+
+impl Input {
+    /// An input given by path, without expected answers.
+    pub fn at(path: &str) -> Self {
+        Input {
+            name: path.to_owned(),
+            path: path.to_owned(),
+            expected: None,
+        }
+    }
+
+    fn new(name: &str, stem: &str) -> Self {
+        Input {
+            name: name.to_owned(),
+            path: format!("{}.txt", stem),
+            expected: Golden::load(format!("{}.answers", stem)),
+        }
+    }
+
+    /// Reads the lines of the input, decrypting it when only the encrypted file exists.
+    pub fn read(&self) -> io::Result<Vec<String>> {
+        match read_input(&self.path) {
+            Err(error) if error.kind() == ErrorKind::NotFound => {
+                match read_input(&format!("{}.{}", self.path, ENCRYPTED_EXT)) {
+                    Err(e) if e.kind() == ErrorKind::NotFound => Err(error),
+                    res => res,
+                }
+            }
+            res => res,
+        }
+    }
+}
+
+/// The inputs of `day` in `dir`: the historical one first, then the named ones by name.
+pub fn list(dir: &str, day: u8) -> Vec<Input> {
+    let mut inputs = Vec::new();
+
+    let stem = format!("{}/d{:02}", dir, day);
+    if exists(&format!("{}.txt", stem)) {
+        inputs.push(Input::new(DEFAULT_NAME, &stem));
+    }
+
+    let mut names = fs::read_dir(&stem)
+        .map(|entries| {
+            entries
+                .filter_map(|e| {
+                    let name = e.ok()?.file_name().into_string().ok()?;
+                    let name = name
+                        .strip_suffix(&format!(".{}", ENCRYPTED_EXT))
+                        .unwrap_or(&name);
+
+                    name.strip_suffix(".txt").map(String::from)
+                })
+                .collect::<Vec<String>>()
+        })
+        .unwrap_or_default();
+    names.sort();
+    names.dedup();
+
+    for name in names {
+        inputs.push(Input::new(&name, &format!("{}/{}", stem, name)));
+    }
+
+    inputs
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+
+    use crate::inputs::{list, Input};
+
+    #[test]
+    fn test_list() {
+        let dir = env::temp_dir().join(format!("aoc-inputs-test-{}", std::process::id()));
+        fs::create_dir_all(dir.join("d03")).unwrap();
+        for (file, content) in [
+            ("d03.txt", "..#\n"),
+            ("d03.answers", "Part 1: 1\nPart 2: 2\n"),
+            ("d03/bob.txt", "#..\n"),
+            ("d03/alice.txt", "...\n"),
+            ("d03/alice.answers", "Part 1: 0\n"),
+            ("d03/notes.md", ""),
+        ] {
+            fs::write(dir.join(file), content).unwrap();
+        }
+        let dir_name = dir.to_str().unwrap();
+
+        let inputs = list(dir_name, 3);
+        assert_eq!(
+            vec!["default", "alice", "bob"],
+            inputs
+                .iter()
+                .map(|i| i.name.as_str())
+                .collect::<Vec<&str>>()
+        );
+        assert_eq!(
+            Some("2"),
+            inputs[0].expected.as_ref().unwrap().part2.as_deref()
+        );
+        assert_eq!(
+            Some("0"),
+            inputs[1].expected.as_ref().unwrap().part1.as_deref()
+        );
+        assert_eq!(None, inputs[2].expected);
+        assert_eq!(vec!["..."], inputs[1].read().unwrap());
+
+        assert!(list(dir_name, 4).is_empty());
+        assert!(Input::at(&format!("{}/d04.txt", dir_name)).read().is_err());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,375 @@
+//! Private leaderboards, read from the JSON served by the Advent of Code website at
+//! `<base_url>/<year>/leaderboard/private/view/<id>.json` or from a saved copy of it.
+
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::time::Duration;
+
+use clap::ValueEnum;
+use serde::{Deserialize, Deserializer};
+
+/// Seconds since the epoch. Older leaderboards give them as strings.
+fn timestamp<'de, D: Deserializer<'de>>(deserializer: D) -> Result<i64, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Timestamp {
+        Number(i64),
+        Text(String),
+    }
+
+    match Timestamp::deserialize(deserializer)? {
+        Timestamp::Number(n) => Ok(n),
+        Timestamp::Text(s) => s.parse().map_err(serde::de::Error::custom),
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Star {
+    #[serde(deserialize_with = "timestamp")]
+    pub get_star_ts: i64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    /// Missing for anonymous users.
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u32,
+    #[serde(default)]
+    pub global_score: u32,
+    #[serde(default, deserialize_with = "timestamp")]
+    pub last_star_ts: i64,
+    /// Stars by day, then by part.
+    #[serde(default)]
+    pub completion_day_level: BTreeMap<u8, BTreeMap<u8, Star>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Leaderboard {
+    pub event: String,
+    pub members: HashMap<String, Member>,
+}
+
+/// How members are ranked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Scheme {
+    /// Local score, as ranked by the website
+    Local,
+    /// Number of stars, the earliest to get them first
+    Stars,
+    /// Global leaderboard points
+    Global,
+    /// Number of stars, then total time from unlock to each star
+    Time,
+}
+
+impl Member {
+    pub fn display_name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+
+    /// Time from the unlock of `day` to the star of `part`, if it was earned.
+    pub fn solve_time(&self, year: i64, day: u8, part: u8) -> Option<i64> {
+        let star = self.completion_day_level.get(&day)?.get(&part)?;
+
+        Some(star.get_star_ts - unlock_time(year, day))
+    }
+
+    fn total_time(&self, year: i64) -> i64 {
+        self.completion_day_level
+            .iter()
+            .flat_map(|(day, parts)| {
+                parts
+                    .values()
+                    .map(move |s| s.get_star_ts - unlock_time(year, *day))
+            })
+            .sum()
+    }
+}
+
+/// Days since the epoch of a date of the proleptic Gregorian calendar.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let doy = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+
+    era * 146_097 + doe - 719_468
+}
+
+/// Puzzles unlock at midnight EST, 05:00 UTC.
+pub fn unlock_time(year: i64, day: u8) -> i64 {
+    days_from_civil(year, 12, day as i64) * 86_400 + 5 * 3_600
+}
+
+impl Leaderboard {
+    pub fn parse(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// The members, best first according to `scheme`.
+    pub fn ranking(&self, year: i64, scheme: Scheme) -> Vec<&Member> {
+        let mut members = self.members.values().collect::<Vec<&Member>>();
+        members.sort_by_key(|m| m.id);
+
+        match scheme {
+            Scheme::Local => {
+                members.sort_by_key(|m| (Reverse(m.local_score), Reverse(m.stars), m.last_star_ts))
+            }
+            Scheme::Stars => members.sort_by_key(|m| (Reverse(m.stars), m.last_star_ts)),
+            Scheme::Global => {
+                members.sort_by_key(|m| (Reverse(m.global_score), Reverse(m.local_score)))
+            }
+            Scheme::Time => members.sort_by_key(|m| (Reverse(m.stars), m.total_time(year))),
+        }
+
+        members
+    }
+}
+
+fn format_time(seconds: i64) -> String {
+    if seconds >= 100 * 3_600 {
+        return String::from(">99h");
+    }
+
+    format!(
+        "{}:{:02}:{:02}",
+        seconds / 3_600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+/// The standings as a table: stars, scores and, for each day someone started, the time from
+/// unlock to the second star, or to the first one followed by `*`.
+pub fn table(leaderboard: &Leaderboard, year: i64, scheme: Scheme) -> String {
+    let members = leaderboard.ranking(year, scheme);
+    let days = members
+        .iter()
+        .flat_map(|m| m.completion_day_level.keys().copied())
+        .collect::<BTreeSet<u8>>();
+    let width = members
+        .iter()
+        .map(|m| m.display_name().chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(4);
+
+    let mut out = format!(
+        "{:>3}  {:width$}  {:>5}  {:>5}  {:>6}",
+        "#",
+        "Name",
+        "Stars",
+        "Local",
+        "Global",
+        width = width
+    );
+    for day in &days {
+        out.push_str(&format!("  {:>9}", day));
+    }
+    out.push('\n');
+
+    for (rank, member) in members.iter().enumerate() {
+        out.push_str(&format!(
+            "{:>3}  {:width$}  {:>5}  {:>5}  {:>6}",
+            rank + 1,
+            member.display_name(),
+            member.stars,
+            member.local_score,
+            member.global_score,
+            width = width
+        ));
+        for &day in &days {
+            let cell = match (
+                member.solve_time(year, day, 1),
+                member.solve_time(year, day, 2),
+            ) {
+                (_, Some(t)) => format_time(t),
+                (Some(t), None) => format!("{}*", format_time(t)),
+                (None, None) => String::from("-"),
+            };
+            out.push_str(&format!("  {:>9}", cell));
+        }
+        out.push('\n');
+    }
+
+    out
+}
+
+/// Fetches the JSON of the leaderboard `id` of `year`, authenticated by the session cookie.
+pub fn fetch(
+    base_url: &str,
+    year: u16,
+    id: u64,
+    session: &str,
+    timeout: Duration,
+) -> Result<String, String> {
+    let url = format!(
+        "{}/{}/leaderboard/private/view/{}.json",
+        base_url.trim_end_matches('/'),
+        year,
+        id
+    );
+
+    let response = ureq::AgentBuilder::new()
+        .timeout(timeout)
+        .redirects(0)
+        .build()
+        .get(&url)
+        .set("Cookie", &format!("session={}", session))
+        .call()
+        .map_err(|e| match e {
+            ureq::Error::Status(status, _) => format!("{} answered {}", url, status),
+            e => format!("Unable to fetch the leaderboard: {}", e),
+        })?;
+
+    // An expired session gets redirected to the login page instead of an error.
+    if response.status() != 200 {
+        return Err(format!(
+            "{} answered {}, is the session cookie valid?",
+            url,
+            response.status()
+        ));
+    }
+
+    response
+        .into_string()
+        .map_err(|e| format!("Unable to read {}: {}", url, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::thread;
+    use std::time::Duration;
+
+    use crate::leaderboard::{fetch, table, unlock_time, Leaderboard, Scheme};
+
+    const JSON: &str = r#"{
+        "event": "2020",
+        "owner_id": 1,
+        "members": {
+            "1": {
+                "id": 1, "name": "Alice", "stars": 3, "local_score": 10, "global_score": 0,
+                "last_star_ts": 1606888800,
+                "completion_day_level": {
+                    "1": {"1": {"get_star_ts": 1606799100}, "2": {"get_star_ts": "1606799400"}},
+                    "2": {"1": {"get_star_ts": 1606888800}}
+                }
+            },
+            "2": {
+                "id": 2, "name": null, "stars": 3, "local_score": 9, "global_score": 12,
+                "last_star_ts": 1606887000,
+                "completion_day_level": {
+                    "1": {"1": {"get_star_ts": 1606798900}, "2": {"get_star_ts": 1606799000}},
+                    "2": {"1": {"get_star_ts": 1606887000}}
+                }
+            },
+            "3": {
+                "id": 3, "name": "Carol", "stars": 0, "local_score": 0, "global_score": 0,
+                "last_star_ts": 0, "completion_day_level": {}
+            }
+        }
+    }"#;
+
+    fn names(leaderboard: &Leaderboard, scheme: Scheme) -> Vec<String> {
+        leaderboard
+            .ranking(2020, scheme)
+            .iter()
+            .map(|m| m.display_name())
+            .collect()
+    }
+
+    #[test]
+    fn test_unlock_time() {
+        // 2020-12-01T05:00:00Z
+        assert_eq!(1606798800, unlock_time(2020, 1));
+        assert_eq!(1606798800 + 24 * 86_400, unlock_time(2020, 25));
+    }
+
+    #[test]
+    fn test_ranking() {
+        let leaderboard = Leaderboard::parse(JSON).unwrap();
+
+        assert_eq!(
+            vec!["Alice", "(anonymous user #2)", "Carol"],
+            names(&leaderboard, Scheme::Local)
+        );
+        assert_eq!(
+            vec!["(anonymous user #2)", "Alice", "Carol"],
+            names(&leaderboard, Scheme::Stars)
+        );
+        assert_eq!(
+            vec!["(anonymous user #2)", "Alice", "Carol"],
+            names(&leaderboard, Scheme::Time)
+        );
+        assert_eq!(
+            vec!["(anonymous user #2)", "Alice", "Carol"],
+            names(&leaderboard, Scheme::Global)
+        );
+
+        let alice = &leaderboard.members["1"];
+        assert_eq!(Some(600), alice.solve_time(2020, 1, 2));
+        assert_eq!(None, alice.solve_time(2020, 2, 2));
+    }
+
+    #[test]
+    fn test_table() {
+        let leaderboard = Leaderboard::parse(JSON).unwrap();
+        let table = table(&leaderboard, 2020, Scheme::Local);
+        let lines = table.lines().collect::<Vec<&str>>();
+
+        assert_eq!(4, lines.len());
+        assert!(lines[0].ends_with("1          2"), "{}", table);
+        assert!(lines[1].contains("Alice"), "{}", table);
+        assert!(lines[1].ends_with("0:10:00   1:00:00*"), "{}", table);
+        assert!(lines[3].ends_with("-          -"), "{}", table);
+    }
+
+    #[test]
+    fn test_fetch() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}/", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = Vec::new();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                request.push(line.trim().to_owned());
+            }
+
+            write!(
+                reader.get_mut(),
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                JSON.len(),
+                JSON
+            )
+            .unwrap();
+            tx.send(request).unwrap();
+        });
+
+        let json = fetch(&base_url, 2020, 1234, "abc", Duration::from_secs(5)).unwrap();
+        let request = rx.recv().unwrap();
+
+        assert_eq!(
+            "GET /2020/leaderboard/private/view/1234.json HTTP/1.1",
+            request[0]
+        );
+        assert!(request.iter().any(|h| h == "Cookie: session=abc"));
+        assert_eq!(3, Leaderboard::parse(&json).unwrap().members.len());
+    }
+}
@@ -0,0 +1,884 @@
+#[cfg(feature = "leaderboard")]
+use std::fs;
+use std::io::{self, Write};
+use std::panic;
+use std::process;
+use std::time::{Duration, Instant};
+
+use clap::{Parser, Subcommand};
+use serde::Serialize;
+
+use aoc_2020::config::{self, Config, Output};
+use aoc_2020::gen::Generator;
+use aoc_2020::{find_input, read_input, Diagnostic};
+
+use crate::cache::Cache;
+use crate::days::{Day, DAYS};
+use crate::inputs::Input;
+
+mod bench;
+mod cache;
+mod days;
+mod golden;
+mod inputs;
+#[cfg(feature = "leaderboard")]
+mod leaderboard;
+#[cfg(feature = "serve")]
+mod serve;
+#[cfg(feature = "tui")]
+mod tui;
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Advent of Code 2020 runner")]
+struct Cli {
+    /// Configuration file to use instead of aoc.toml
+    #[arg(long, global = true)]
+    config: Option<String>,
+    /// Directory of the puzzle inputs, overriding the configuration
+    #[arg(long, global = true)]
+    input_dir: Option<String>,
+    /// Directory of the cached answers, overriding the configuration
+    #[arg(long, global = true)]
+    cache_dir: Option<String>,
+    /// Format of the answers, text or json, overriding the configuration
+    #[arg(long, global = true)]
+    format: Option<Output>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve a day
+    Run {
+        day: u8,
+        /// Input file to use instead of every input of the day
+        #[arg(long)]
+        input: Option<String>,
+        /// Use the example input of the day, examples/dNN.txt
+        #[arg(long, conflicts_with = "input")]
+        example: bool,
+        /// Always solve, ignoring cached answers
+        #[arg(long)]
+        no_cache: bool,
+        /// Named implementation to use for the parts that have one, bypassing the cache
+        #[arg(long = "impl", value_name = "NAME")]
+        implementation: Option<String>,
+        /// Print the intermediate steps of the solution before the answers
+        #[arg(long)]
+        explain: bool,
+    },
+    /// Time the implementations of a day side by side
+    Bench {
+        day: u8,
+        /// Input file to use instead of input/dNN.txt
+        #[arg(long)]
+        input: Option<String>,
+        /// Number of runs of each implementation, the median is reported [default: bench.runs]
+        #[arg(long)]
+        runs: Option<usize>,
+    },
+    /// Check that the answers of a day, or of every day, match the expected ones on each
+    /// input, and that all its implementations agree
+    Verify { day: Option<u8> },
+    /// Serve the solvers over HTTP
+    #[cfg(feature = "serve")]
+    Serve {
+        #[arg(long, default_value = serve::DEFAULT_ADDR)]
+        addr: String,
+        /// Largest accepted puzzle input, in bytes
+        #[arg(long, default_value_t = serve::Limits::default().max_body)]
+        max_body: usize,
+        /// Time limit of each request, in seconds [default: timeouts.serve]
+        #[arg(long)]
+        timeout: Option<f64>,
+        /// Number of requests handled at once
+        #[arg(long, default_value_t = serve::Limits::default().max_concurrent)]
+        max_concurrent: usize,
+    },
+    /// Browse the calendar in a terminal dashboard
+    #[cfg(feature = "tui")]
+    Tui,
+    /// Solve every implemented day
+    All {
+        /// Always solve, ignoring cached answers
+        #[arg(long)]
+        no_cache: bool,
+    },
+    /// Print a random input for a day, and its answers on stderr when they are known
+    Gen {
+        day: u8,
+        /// Scale of the input, its meaning depends on the day
+        #[arg(long, default_value_t = 1000)]
+        size: usize,
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+    },
+    /// Compare a day's solver with its reference solver, or with the planted answers, on
+    /// generated inputs
+    Difftest {
+        day: u8,
+        /// Number of inputs to generate, with consecutive seeds
+        #[arg(long, default_value_t = 100)]
+        cases: u64,
+        /// Scale of the inputs, kept small so that the reference solvers stay fast
+        #[arg(long, default_value_t = 5)]
+        size: usize,
+        /// Seed of the first input
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+    },
+    /// Show the standings of a private leaderboard
+    #[cfg(feature = "leaderboard")]
+    Leaderboard {
+        /// Leaderboard to fetch, the user id of its owner [default: leaderboard.id]
+        id: Option<u64>,
+        /// Read the leaderboard JSON from a file instead of fetching it
+        #[arg(long, conflicts_with = "id")]
+        file: Option<String>,
+        #[arg(long, value_enum, default_value_t = leaderboard::Scheme::Local)]
+        sort: leaderboard::Scheme,
+    },
+    /// Print the effective configuration, or the value of one setting such as `bench.runs`
+    Config { key: Option<String> },
+}
+
+#[derive(Serialize)]
+struct Report<'a> {
+    day: u8,
+    input: &'a str,
+    part1: &'a str,
+    part2: &'a str,
+    duration_us: u64,
+    cached: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expected: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    steps: Option<Vec<String>>,
+}
+
+struct Answers {
+    part1: String,
+    part2: String,
+    duration: Duration,
+    cached: bool,
+}
+
+fn solve(
+    day: &Day,
+    input: &[String],
+    cache: Option<&Cache>,
+    implementation: Option<&str>,
+) -> Result<Answers, Diagnostic> {
+    if let Some(name) = implementation {
+        let start = Instant::now();
+        let (part1, part2) = day.solve_with(name, input)?;
+
+        return Ok(Answers {
+            part1,
+            part2,
+            duration: start.elapsed(),
+            cached: false,
+        });
+    }
+
+    let input_hash = cache::hash_input(input);
+    let solver_version = day.version.to_string();
+
+    if let Some(entry) = cache.and_then(|c| c.get(day.number, &input_hash, &solver_version)) {
+        return Ok(Answers {
+            part1: entry.part1,
+            part2: entry.part2,
+            duration: Duration::from_micros(entry.duration_us),
+            cached: true,
+        });
+    }
+
+    let start = Instant::now();
+    let (part1, part2) = (day.solve)(input)?;
+    let duration = start.elapsed();
+
+    if let Some(cache) = cache {
+        let entry = cache::Entry {
+            day: day.number,
+            input_hash,
+            solver_version,
+            part1: part1.clone(),
+            part2: part2.clone(),
+            duration_us: duration.as_micros() as u64,
+        };
+        if let Err(e) = cache.put(&entry) {
+            eprintln!("Unable to cache answers of day {}: {}", day.number, e);
+        }
+    }
+
+    Ok(Answers {
+        part1,
+        part2,
+        duration,
+        cached: false,
+    })
+}
+
+/// Prints the answers of `input`, with the explanation steps when there are some, and returns
+/// whether they are the expected ones.
+fn print_answers(day: &Day, input: &Input, answers: &Answers, steps: Option<Vec<String>>) -> bool {
+    let mismatches = input
+        .expected
+        .as_ref()
+        .map(|e| e.mismatches(&answers.part1, &answers.part2))
+        .unwrap_or_default();
+
+    if config::get().output == Output::Json {
+        let report = Report {
+            day: day.number,
+            input: &input.name,
+            part1: &answers.part1,
+            part2: &answers.part2,
+            duration_us: answers.duration.as_micros() as u64,
+            cached: answers.cached,
+            expected: input.expected.as_ref().map(|_| mismatches.is_empty()),
+            steps,
+        };
+        println!("{}", serde_json::to_string(&report).unwrap());
+
+        return mismatches.is_empty();
+    }
+
+    for step in steps.into_iter().flatten() {
+        println!("{}", step);
+    }
+    println!("Part 1: {}", answers.part1);
+    println!("Part 2: {}", answers.part2);
+    println!(
+        "Duration: {:.3}ms{}",
+        answers.duration.as_micros() as f64 / 1000.0,
+        if answers.cached { " (cached)" } else { "" }
+    );
+    for (part, expected) in &mismatches {
+        println!("Part {} should be {}", part, expected);
+    }
+
+    mismatches.is_empty()
+}
+
+fn get_cache(no_cache: bool) -> Option<Cache> {
+    if no_cache {
+        None
+    } else {
+        Some(Cache::new(&config::get().cache_dir))
+    }
+}
+
+fn get_day(number: u8) -> Option<&'static Day> {
+    let day = days::get(number);
+    if day.is_none() {
+        eprintln!("Invalid day: {}", number);
+    }
+
+    day
+}
+
+/// Reads the input of `day` from `input`, or from its default location, along with the path
+/// to report in errors.
+fn load_input(day: &Day, input: Option<String>) -> Option<(String, Vec<String>)> {
+    let (path, lines) = match input {
+        Some(path) => (path.clone(), read_input(&path)),
+        None => (day.input_path(), find_input(&day.input_name())),
+    };
+
+    match lines {
+        Ok(lines) => Some((path, lines)),
+        Err(e) => {
+            eprintln!("Unable to read {}: {}", path, e);
+            None
+        }
+    }
+}
+
+/// The inputs to solve: the file at `path`, or every input of `day`.
+fn get_inputs(day: &Day, path: Option<String>) -> Option<Vec<Input>> {
+    if let Some(path) = path {
+        return Some(vec![Input::at(&path)]);
+    }
+
+    let dir = &config::get().input_dir;
+    let inputs = inputs::list(dir, day.number);
+    if inputs.is_empty() {
+        eprintln!(
+            "No input for day {} in {}/, expected {} or d{:02}/<name>.txt",
+            day.number,
+            dir,
+            day.input_name(),
+            day.number
+        );
+        return None;
+    }
+
+    Some(inputs)
+}
+
+/// Solves `input` and prints the answers, returning whether they are the expected ones.
+fn run_input(
+    day: &Day,
+    input: &Input,
+    cache: Option<&Cache>,
+    implementation: Option<&str>,
+    explain: bool,
+) -> bool {
+    let lines = match input.read() {
+        Ok(lines) => lines,
+        Err(e) => {
+            eprintln!("Unable to read {}: {}", input.path, e);
+            return false;
+        }
+    };
+
+    let steps = match day
+        .explain
+        .filter(|_| explain)
+        .map(|explain| explain(&lines))
+    {
+        Some(Ok(steps)) => Some(steps.iter().map(|s| s.to_string()).collect()),
+        Some(Err(e)) => {
+            eprintln!("{}", e.with_file(&input.path));
+            return false;
+        }
+        None => None,
+    };
+
+    match solve(day, &lines, cache, implementation) {
+        Ok(answers) => print_answers(day, input, &answers, steps),
+        Err(e) => {
+            eprintln!("{}", e.with_file(&input.path));
+            false
+        }
+    }
+}
+
+fn run(
+    day: u8,
+    input: Option<String>,
+    example: bool,
+    no_cache: bool,
+    implementation: Option<String>,
+    explain: bool,
+) -> bool {
+    let day = match get_day(day) {
+        Some(day) => day,
+        None => return false,
+    };
+
+    if explain && day.explain.is_none() {
+        eprintln!("Day {} has no explanation", day.number);
+        return false;
+    }
+
+    if let Some(name) = &implementation {
+        let names = day.implementation_names();
+        if !names.contains(&name.as_str()) {
+            eprintln!(
+                "Day {} has no implementation named {} (available: {})",
+                day.number,
+                name,
+                if names.is_empty() {
+                    String::from("none")
+                } else {
+                    names.join(", ")
+                }
+            );
+            return false;
+        }
+    }
+
+    let input = if example {
+        Some(format!(
+            "{}/{}",
+            config::get().example_dir,
+            day.input_name()
+        ))
+    } else {
+        input
+    };
+    let inputs = match get_inputs(day, input) {
+        Some(inputs) => inputs,
+        None => return false,
+    };
+
+    let cache = get_cache(no_cache);
+    let text = config::get().output == Output::Text;
+    let mut ok = true;
+    for (i, input) in inputs.iter().enumerate() {
+        if text && inputs.len() > 1 {
+            if i > 0 {
+                println!();
+            }
+            println!("Input {} ({})", input.name, input.path);
+        }
+
+        ok &= run_input(
+            day,
+            input,
+            cache.as_ref(),
+            implementation.as_deref(),
+            explain,
+        );
+    }
+
+    ok
+}
+
+fn run_all(no_cache: bool) -> bool {
+    let cache = get_cache(no_cache);
+    let text = config::get().output == Output::Text;
+    let mut total = Duration::default();
+    let mut ok = true;
+
+    for day in DAYS.iter().filter(|d| d.implemented) {
+        if text {
+            println!("Day {:02}", day.number);
+        }
+
+        let input = inputs::list(&config::get().input_dir, day.number)
+            .into_iter()
+            .next()
+            .ok_or_else(|| String::from("not found"))
+            .and_then(|input| match input.read() {
+                Ok(lines) => Ok((input, lines)),
+                Err(e) => Err(e.to_string()),
+            });
+        let (input, lines) = match input {
+            Ok(input) => input,
+            Err(e) if text => {
+                println!("No input: {}", e);
+                println!();
+                continue;
+            }
+            Err(e) => {
+                eprintln!("Day {:02}: no input: {}", day.number, e);
+                continue;
+            }
+        };
+
+        match solve(day, &lines, cache.as_ref(), None) {
+            Ok(answers) => {
+                ok &= print_answers(day, &input, &answers, None);
+                total += answers.duration;
+            }
+            Err(e) => {
+                eprintln!("{}", e.with_file(&input.path));
+                ok = false;
+            }
+        }
+        if text {
+            println!();
+        }
+    }
+
+    if text {
+        println!("Total: {:.3}ms", total.as_micros() as f64 / 1000.0);
+    }
+
+    ok
+}
+
+fn bench(day: u8, input: Option<String>, runs: Option<usize>) -> bool {
+    let day = match get_day(day) {
+        Some(day) => day,
+        None => return false,
+    };
+    let (path, lines) = match load_input(day, input) {
+        Some(input) => input,
+        None => return false,
+    };
+
+    match bench::bench(day, &lines, runs.unwrap_or(config::get().bench.runs)) {
+        Ok(()) => true,
+        Err(e) => {
+            eprintln!("{}", e.with_file(&path));
+            false
+        }
+    }
+}
+
+/// Checks that the solver of `day` gives the expected answers for `input`, and that every
+/// implementation gives the same answers as the solver.
+fn verify_input(day: &Day, input: &Input) -> bool {
+    let name = format!("Day {:02} {}", day.number, input.name);
+    let lines = match input.read() {
+        Ok(lines) => lines,
+        Err(e) => {
+            eprintln!("Unable to read {}: {}", input.path, e);
+            return false;
+        }
+    };
+
+    let answers = match (day.solve)(&lines) {
+        Ok((p1, p2)) => [p1, p2],
+        Err(e) => {
+            eprintln!("{}", e.with_file(&input.path));
+            return false;
+        }
+    };
+
+    let mut ok = true;
+    let mut checks = Vec::new();
+
+    if let Some(expected) = &input.expected {
+        let mismatches = expected.mismatches(&answers[0], &answers[1]);
+        for (part, expected) in &mismatches {
+            println!(
+                "{}: part {} is {}, expected {}",
+                name,
+                part,
+                answers[*part as usize - 1],
+                expected
+            );
+        }
+        ok &= mismatches.is_empty();
+        checks.push(String::from("answers as expected"));
+    }
+
+    for implementation in day.implementations {
+        let expected = &answers[implementation.part as usize - 1];
+
+        match (implementation.solve)(&lines) {
+            Ok(answer) if &answer == expected => {}
+            Ok(answer) => {
+                println!(
+                    "{}: part {} with {} is {}, expected {}",
+                    name, implementation.part, implementation.name, answer, expected
+                );
+                ok = false;
+            }
+            Err(e) => {
+                eprintln!("{}", e.with_file(&input.path));
+                ok = false;
+            }
+        }
+    }
+    if !day.implementations.is_empty() {
+        checks.push(format!("{} agree", day.implementation_names().join(", ")));
+    }
+
+    if ok {
+        if checks.is_empty() {
+            println!(
+                "{}: no expected answers nor alternative implementations",
+                name
+            );
+        } else {
+            println!("{}: {}", name, checks.join(", "));
+        }
+    }
+
+    ok
+}
+
+/// Verifies every input of `day`.
+fn verify_day(day: &Day) -> bool {
+    let inputs = match get_inputs(day, None) {
+        Some(inputs) => inputs,
+        None => return false,
+    };
+
+    let mut ok = true;
+    for input in &inputs {
+        ok &= verify_input(day, input);
+    }
+
+    ok
+}
+
+fn verify(day: Option<u8>) -> bool {
+    match day {
+        Some(number) => match get_day(number) {
+            Some(day) => verify_day(day),
+            None => false,
+        },
+        None => {
+            let mut ok = true;
+            for day in DAYS.iter().filter(|d| d.implemented) {
+                ok &= verify_day(day);
+            }
+
+            ok
+        }
+    }
+}
+
+fn get_generator(number: u8) -> Option<(&'static Day, Generator)> {
+    let day = get_day(number)?;
+
+    match day.generate {
+        Some(generate) => Some((day, generate)),
+        None => {
+            eprintln!("No generator for day {}", number);
+            None
+        }
+    }
+}
+
+fn generate(day: u8, size: usize, seed: u64) -> bool {
+    let generate = match get_generator(day) {
+        Some((_, generate)) => generate,
+        None => return false,
+    };
+
+    let gen = generate(size, seed);
+
+    let mut out = io::stdout().lock();
+    for l in &gen.input {
+        if writeln!(out, "{}", l).is_err() {
+            return false;
+        }
+    }
+
+    if let Some(part1) = gen.part1 {
+        eprintln!("Part 1: {}", part1);
+    }
+    if let Some(part2) = gen.part2 {
+        eprintln!("Part 2: {}", part2);
+    }
+
+    true
+}
+
+fn difftest(day: u8, cases: u64, size: usize, first_seed: u64) -> bool {
+    let (day, generate) = match get_generator(day) {
+        Some(g) => g,
+        None => return false,
+    };
+
+    for seed in first_seed..first_seed + cases {
+        let gen = generate(size, seed);
+        let case = format!("--size {} --seed {}", size, seed);
+
+        let expected = match day.reference {
+            Some(reference) => match reference(&gen.input) {
+                Ok((p1, p2)) => (Some(p1), Some(p2)),
+                Err(e) => {
+                    eprintln!("Reference solver failed with {}:\n{}", case, e);
+                    return false;
+                }
+            },
+            None => (gen.part1.clone(), gen.part2.clone()),
+        };
+        if expected == (None, None) {
+            eprintln!(
+                "No reference solver nor planted answers for day {}",
+                day.number
+            );
+            return false;
+        }
+
+        let answers = match panic::catch_unwind(|| (day.solve)(&gen.input)) {
+            Ok(Ok(answers)) => answers,
+            Ok(Err(e)) => {
+                eprintln!("Solver failed with {}:\n{}", case, e);
+                return false;
+            }
+            Err(_) => {
+                eprintln!("Solver panicked with {}", case);
+                return false;
+            }
+        };
+
+        let parts = [(1, expected.0, answers.0), (2, expected.1, answers.1)];
+        for (part, expected, answer) in parts.iter() {
+            match expected {
+                Some(expected) if expected != answer => {
+                    println!("Mismatch on part {} with {}", part, case);
+                    println!("  expected: {}", expected);
+                    println!("  got:      {}", answer);
+                    println!("The input is printed by `aoc gen {} {}`", day.number, case);
+                    return false;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    println!("Day {:02}: {} cases agree", day.number, cases);
+
+    true
+}
+
+#[cfg(feature = "serve")]
+fn serve(addr: &str, max_body: usize, timeout: Option<f64>, max_concurrent: usize) -> bool {
+    let secs = timeout.unwrap_or(config::get().timeouts.serve);
+    let timeout = match Duration::try_from_secs_f64(secs) {
+        Ok(timeout) => timeout,
+        Err(_) => {
+            eprintln!("Invalid timeout: {}", secs);
+            return false;
+        }
+    };
+
+    let server = match tiny_http::Server::http(addr) {
+        Ok(server) => server,
+        Err(e) => {
+            eprintln!("Unable to listen on {}: {}", addr, e);
+            return false;
+        }
+    };
+    println!("Listening on http://{}", addr);
+
+    let limits = serve::Limits {
+        max_body,
+        timeout,
+        max_concurrent,
+    };
+    serve::run(&server, limits);
+
+    true
+}
+
+#[cfg(feature = "leaderboard")]
+fn show_leaderboard(id: Option<u64>, file: Option<String>, sort: leaderboard::Scheme) -> bool {
+    let config = config::get();
+    let timeout = match Duration::try_from_secs_f64(config.timeouts.http) {
+        Ok(timeout) => timeout,
+        Err(_) => {
+            eprintln!("Invalid timeouts.http: {}", config.timeouts.http);
+            return false;
+        }
+    };
+
+    let json = match file {
+        Some(path) => {
+            fs::read_to_string(&path).map_err(|e| format!("Unable to read {}: {}", path, e))
+        }
+        None => match id.or(config.leaderboard.id) {
+            Some(id) => fs::read_to_string(&config.session_file)
+                .map_err(|e| {
+                    format!(
+                        "Unable to read session cookie from {}: {}",
+                        config.session_file, e
+                    )
+                })
+                .and_then(|session| {
+                    leaderboard::fetch(
+                        &config.leaderboard.base_url,
+                        config.year,
+                        id,
+                        session.trim(),
+                        timeout,
+                    )
+                }),
+            None => Err(String::from(
+                "No leaderboard: pass its id or set leaderboard.id in aoc.toml",
+            )),
+        },
+    };
+
+    let board = match json.and_then(|json| {
+        leaderboard::Leaderboard::parse(&json).map_err(|e| format!("Invalid leaderboard: {}", e))
+    }) {
+        Ok(board) => board,
+        Err(e) => {
+            eprintln!("{}", e);
+            return false;
+        }
+    };
+
+    let year = board.event.parse().unwrap_or(config.year as i64);
+    print!("{}", leaderboard::table(&board, year, sort));
+
+    true
+}
+
+/// Prints the configuration as TOML, or the value of the setting `key`.
+fn show_config(key: Option<String>) -> bool {
+    let config = config::get();
+
+    match key {
+        Some(key) => match config.get(&key) {
+            Some(value) => {
+                println!("{}", value);
+                true
+            }
+            None => {
+                eprintln!("Unknown setting: {}", key);
+                false
+            }
+        },
+        None => {
+            print!("{}", toml::to_string(config).unwrap());
+            true
+        }
+    }
+}
+
+/// Loads the configuration and applies the command line overrides.
+fn load_config(cli: &Cli) -> Option<Config> {
+    let mut config = match Config::load(cli.config.as_deref()) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{}", e);
+            return None;
+        }
+    };
+
+    if let Some(dir) = &cli.input_dir {
+        config.input_dir = dir.clone();
+    }
+    if let Some(dir) = &cli.cache_dir {
+        config.cache_dir = dir.clone();
+    }
+    if let Some(format) = cli.format {
+        config.output = format;
+    }
+
+    Some(config)
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    let config = load_config(&cli).unwrap_or_else(|| process::exit(1));
+    config::init(config);
+
+    let ok = match cli.command {
+        Command::Run {
+            day,
+            input,
+            example,
+            no_cache,
+            implementation,
+            explain,
+        } => run(day, input, example, no_cache, implementation, explain),
+        Command::Bench { day, input, runs } => bench(day, input, runs),
+        Command::Verify { day } => verify(day),
+        #[cfg(feature = "serve")]
+        Command::Serve {
+            addr,
+            max_body,
+            timeout,
+            max_concurrent,
+        } => serve(&addr, max_body, timeout, max_concurrent),
+        #[cfg(feature = "tui")]
+        Command::Tui => match tui::run() {
+            Ok(()) => true,
+            Err(e) => {
+                eprintln!("Terminal error: {}", e);
+                false
+            }
+        },
+        Command::All { no_cache } => run_all(no_cache),
+        Command::Gen { day, size, seed } => generate(day, size, seed),
+        Command::Difftest {
+            day,
+            cases,
+            size,
+            seed,
+        } => difftest(day, cases, size, seed),
+        #[cfg(feature = "leaderboard")]
+        Command::Leaderboard { id, file, sort } => show_leaderboard(id, file, sort),
+        Command::Config { key } => show_config(key),
+    };
+
+    if !ok {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,392 @@
+//! Local HTTP API exposing the solvers.
+//!
+//! - `GET /days` lists the implemented days.
+//! - `POST /solve/{day}[?impl=<name>&timeout=<seconds>]` solves the puzzle input sent as body,
+//!   within the given timeout if it's shorter than the server's.
+//!
+//! Answers and errors are JSON. A solver that runs past the timeout gets a `504`, but can't be
+//! interrupted: it keeps running in its thread until it's done, and keeps its request slot
+//! until then. Requests beyond the slots get a `503`.
+
+use std::io::Read;
+use std::panic;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use crate::days::{self, DAYS};
+
+pub const DEFAULT_ADDR: &str = "127.0.0.1:8020";
+
+/// Limits applied to each request.
+#[derive(Debug, Clone, Copy)]
+pub struct Limits {
+    /// Largest accepted body, in bytes.
+    pub max_body: usize,
+    pub timeout: Duration,
+    /// Number of requests handled at once, including the solvers still running past their
+    /// timeout.
+    pub max_concurrent: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Limits {
+            max_body: 1 << 20,
+            timeout: Duration::from_secs(10),
+            max_concurrent: 8,
+        }
+    }
+}
+
+/// A request slot, given back when dropped.
+struct Slot(Arc<AtomicUsize>);
+
+impl Slot {
+    /// Takes one of the `max` slots counted by `used`, if one is free.
+    fn acquire(used: &Arc<AtomicUsize>, max: usize) -> Option<Slot> {
+        used.fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| {
+            if n < max {
+                Some(n + 1)
+            } else {
+                None
+            }
+        })
+        .ok()
+        .map(|_| Slot(Arc::clone(used)))
+    }
+}
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+#[derive(Serialize)]
+struct DayInfo {
+    day: u8,
+    implementations: Vec<&'static str>,
+    explain: bool,
+}
+
+#[derive(Serialize)]
+struct Solution {
+    day: u8,
+    part1: String,
+    part2: String,
+    duration_us: u64,
+}
+
+#[derive(Serialize)]
+struct Error {
+    error: String,
+}
+
+type Reply = (u16, String);
+
+fn json(status: u16, value: &impl Serialize) -> Reply {
+    (status, serde_json::to_string(value).unwrap())
+}
+
+fn error(status: u16, message: impl Into<String>) -> Reply {
+    json(
+        status,
+        &Error {
+            error: message.into(),
+        },
+    )
+}
+
+fn list_days() -> Reply {
+    let days = DAYS
+        .iter()
+        .filter(|d| d.implemented)
+        .map(|d| DayInfo {
+            day: d.number,
+            implementations: d.implementation_names(),
+            explain: d.explain.is_some(),
+        })
+        .collect::<Vec<DayInfo>>();
+
+    json(200, &days)
+}
+
+/// Reads the body, up to `max` bytes.
+fn read_body(request: &mut Request, max: usize) -> Result<String, Reply> {
+    let too_large = || error(413, format!("body larger than {} bytes", max));
+
+    if request.body_length().is_some_and(|l| l > max) {
+        return Err(too_large());
+    }
+
+    let mut body = Vec::new();
+    request
+        .as_reader()
+        .take(max as u64 + 1)
+        .read_to_end(&mut body)
+        .map_err(|e| error(400, format!("unable to read body: {}", e)))?;
+    if body.len() > max {
+        return Err(too_large());
+    }
+
+    String::from_utf8(body).map_err(|_| error(400, "body isn't valid UTF-8"))
+}
+
+/// The value of the parameter `name` of the query string.
+fn param<'a>(query: Option<&'a str>, name: &str) -> Option<&'a str> {
+    query?
+        .split('&')
+        .filter_map(|p| p.split_once('='))
+        .find(|&(key, _)| key == name)
+        .map(|(_, value)| value)
+}
+
+fn solve(
+    request: &mut Request,
+    day: &str,
+    query: Option<&str>,
+    limits: Limits,
+    slot: Slot,
+) -> Reply {
+    let day = match day.parse().ok().and_then(days::get) {
+        Some(day) if day.implemented => day,
+        _ => return error(404, format!("no such day: {}", day)),
+    };
+
+    let implementation = match param(query, "impl") {
+        Some(name) if day.implementation_names().contains(&name) => Some(name.to_owned()),
+        Some(name) => {
+            return error(
+                400,
+                format!("day {} has no implementation named {}", day.number, name),
+            )
+        }
+        None => None,
+    };
+
+    let timeout = match param(query, "timeout") {
+        Some(secs) => match secs.parse().map(Duration::try_from_secs_f64) {
+            Ok(Ok(timeout)) => timeout.min(limits.timeout),
+            _ => return error(400, format!("invalid timeout: {}", secs)),
+        },
+        None => limits.timeout,
+    };
+
+    let body = match read_body(request, limits.max_body) {
+        Ok(body) => body,
+        Err(reply) => return reply,
+    };
+    let input = body.lines().map(String::from).collect::<Vec<String>>();
+
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let _slot = slot;
+        let start = Instant::now();
+        let res = panic::catch_unwind(|| match &implementation {
+            Some(name) => day.solve_with(name, &input),
+            None => (day.solve)(&input),
+        });
+        let _ = tx.send((res, start.elapsed()));
+    });
+
+    match rx.recv_timeout(timeout) {
+        Ok((Ok(Ok((part1, part2))), duration)) => json(
+            200,
+            &Solution {
+                day: day.number,
+                part1,
+                part2,
+                duration_us: duration.as_micros() as u64,
+            },
+        ),
+        Ok((Ok(Err(diagnostic)), _)) => error(400, diagnostic.with_file("<body>").to_string()),
+        Ok((Err(_), _)) | Err(mpsc::RecvTimeoutError::Disconnected) => {
+            error(500, "the solver panicked")
+        }
+        Err(mpsc::RecvTimeoutError::Timeout) => error(
+            504,
+            format!("no answer after {:.3}s", timeout.as_secs_f64()),
+        ),
+    }
+}
+
+fn handle(mut request: Request, limits: Limits, slot: Slot) {
+    let url = request.url().to_owned();
+    let (path, query) = match url.split_once('?') {
+        Some((path, query)) => (path, Some(query)),
+        None => (url.as_str(), None),
+    };
+
+    let (status, body) = match (request.method(), path) {
+        (Method::Get, "/days") => list_days(),
+        (Method::Post, p) if p.starts_with("/solve/") => {
+            solve(&mut request, &p["/solve/".len()..], query, limits, slot)
+        }
+        (_, "/days") => error(405, "use GET"),
+        (_, p) if p.starts_with("/solve/") => error(405, "use POST"),
+        _ => error(404, format!("no such route: {}", path)),
+    };
+
+    respond(request, &url, (status, body));
+}
+
+fn respond(request: Request, url: &str, (status, body): Reply) {
+    let response = Response::from_string(body)
+        .with_status_code(status)
+        .with_header(Header::from_bytes("Content-Type", "application/json").unwrap());
+    if let Err(e) = request.respond(response) {
+        eprintln!("Unable to respond to {}: {}", url, e);
+    }
+}
+
+/// Handles the requests of `server` until it's unblocked, each one in its own thread, up to
+/// `limits.max_concurrent` at once.
+pub fn run(server: &Server, limits: Limits) {
+    let used = Arc::new(AtomicUsize::new(0));
+
+    for request in server.incoming_requests() {
+        match Slot::acquire(&used, limits.max_concurrent) {
+            Some(slot) => {
+                thread::spawn(move || handle(request, limits, slot));
+            }
+            None => {
+                let url = request.url().to_owned();
+                respond(request, &url, error(503, "too many requests in progress"));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Write};
+    use std::net::{SocketAddr, TcpStream};
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+
+    use tiny_http::Server;
+
+    use crate::serve::{run, Limits};
+
+    fn start(limits: Limits) -> SocketAddr {
+        let server = Arc::new(Server::http("127.0.0.1:0").unwrap());
+        let addr = server.server_addr().to_ip().unwrap();
+        thread::spawn(move || run(&server, limits));
+
+        addr
+    }
+
+    /// Sends a request and returns the status and body of the response.
+    fn request(addr: SocketAddr, method: &str, path: &str, body: &str) -> (u16, String) {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        write!(
+            stream,
+            "{} {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\nContent-Length: {}\r\n\r\n{}",
+            method,
+            path,
+            body.len(),
+            body
+        )
+        .unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+
+        let status = response[9..12].parse().unwrap();
+        let body = response.split_once("\r\n\r\n").unwrap().1.to_owned();
+
+        (status, body)
+    }
+
+    #[test]
+    fn test_days() {
+        let addr = start(Limits::default());
+        let (status, body) = request(addr, "GET", "/days", "");
+
+        assert_eq!(200, status);
+        let days: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(16, days.as_array().unwrap().len());
+        assert_eq!(15, days[14]["day"]);
+        assert_eq!(
+            serde_json::json!(["hashmap", "vec"]),
+            days[14]["implementations"]
+        );
+    }
+
+    #[test]
+    fn test_solve() {
+        let addr = start(Limits::default());
+        let (status, body) = request(addr, "POST", "/solve/1", "1721\n979\n366\n299\n675\n1456\n");
+
+        assert_eq!(200, status);
+        let res: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!("514579", res["part1"]);
+        assert_eq!("241861950", res["part2"]);
+
+        let (status, body) = request(addr, "POST", "/solve/15?impl=vec", "0,3,6\n");
+        assert_eq!(200, status, "{}", body);
+        let res: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!("436", res["part1"]);
+    }
+
+    #[test]
+    fn test_errors() {
+        let addr = start(Limits {
+            max_body: 16,
+            timeout: Duration::from_millis(50),
+            ..Limits::default()
+        });
+
+        assert_eq!(404, request(addr, "POST", "/solve/25", "").0);
+        assert_eq!(404, request(addr, "GET", "/", "").0);
+        assert_eq!(405, request(addr, "GET", "/solve/1", "").0);
+        assert_eq!(
+            400,
+            request(addr, "POST", "/solve/15?impl=btree", "0,3,6").0
+        );
+        assert_eq!(413, request(addr, "POST", "/solve/1", &"1\n".repeat(20)).0);
+        assert_eq!(504, request(addr, "POST", "/solve/15", "0,3,6").0);
+        for timeout in &["-1", "NaN", "inf", "1e400", "abc"] {
+            let path = format!("/solve/1?timeout={}", timeout);
+            assert_eq!(400, request(addr, "POST", &path, "1721").0, "{}", timeout);
+        }
+
+        let (status, body) = request(addr, "POST", "/solve/1", "1721\nabc\n");
+        assert_eq!(400, status);
+        assert!(body.contains("invalid number"), "{}", body);
+
+        // Used to abort the whole server on the allocation of its table.
+        let (status, body) = request(addr, "POST", "/solve/15?impl=vec", "0,4294967296000\n");
+        assert_eq!(400, status, "{}", body);
+        assert_eq!(200, request(addr, "GET", "/days", "").0);
+    }
+
+    #[test]
+    fn test_timeout() {
+        let addr = start(Limits::default());
+        let (status, body) = request(addr, "POST", "/solve/15?impl=vec&timeout=0.05", "0,3,6");
+
+        assert_eq!(504, status);
+        assert!(body.contains("no answer after 0.050s"), "{}", body);
+    }
+
+    #[test]
+    fn test_max_concurrent() {
+        let addr = start(Limits {
+            timeout: Duration::from_millis(50),
+            max_concurrent: 1,
+            ..Limits::default()
+        });
+
+        // The solver keeps its slot past the timeout, until it's done.
+        assert_eq!(504, request(addr, "POST", "/solve/15", "0,3,6").0);
+        let (status, body) = request(addr, "GET", "/days", "");
+        assert_eq!(503, status, "{}", body);
+    }
+}
@@ -0,0 +1,543 @@
+//! Terminal dashboard of the calendar.
+//!
+//! Lists the days with their last answers and timings, runs a day or its tests in the
+//! background and shows the output, with the answers compared to the golden ones. The days
+//! that can render their solution step by step, like the seat layouts of day 11, open in a
+//! visualization.
+
+use std::io;
+use std::process::Command;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::Duration;
+
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Cell, Paragraph, Row, Table, TableState, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+
+use aoc_2020::{config, find_input};
+
+use crate::cache::{self, Cache};
+use crate::days::{Day, DAYS};
+use crate::golden::{self, Golden};
+use crate::Answers;
+
+/// Delay between two polls of the terminal, and between two frames of a visualization.
+const TICK: Duration = Duration::from_millis(100);
+
+const HELP: &str = "↑↓ select · enter run · t test · v visualize · q quit";
+const VISUALIZATION_HELP: &str = "←→ step · a play · p part · esc back";
+
+/// A day of the calendar and what's known of its last run.
+struct Entry {
+    day: &'static Day,
+    golden: Option<Golden>,
+    answers: Option<(String, String)>,
+    duration: Option<Duration>,
+    /// Output of the last run or test of the day.
+    output: Vec<Line<'static>>,
+    running: bool,
+}
+
+/// Result of a job running in the background, for the entry at the given index.
+enum Message {
+    Solved(usize, Result<Answers, String>),
+    Tested(usize, bool, String),
+}
+
+struct Visualization {
+    day: &'static Day,
+    input: Vec<String>,
+    part: u8,
+    frames: Vec<Vec<String>>,
+    frame: usize,
+    playing: bool,
+}
+
+struct App {
+    entries: Vec<Entry>,
+    table: TableState,
+    visualization: Option<Visualization>,
+    status: String,
+    tx: Sender<Message>,
+    rx: Receiver<Message>,
+}
+
+/// Answers of `day` cached for its current input and solver, if any.
+fn cached_answers(day: &Day) -> Option<cache::Entry> {
+    let input = find_input(&day.input_name()).ok()?;
+
+    Cache::new(&config::get().cache_dir).get(day.number, &cache::hash_input(&input), day.version)
+}
+
+fn text_lines(text: &str, style: Style) -> Vec<Line<'static>> {
+    text.lines()
+        .map(|l| Line::styled(l.to_owned(), style))
+        .collect()
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.3}ms", duration.as_micros() as f64 / 1000.0)
+}
+
+impl Entry {
+    fn new(day: &'static Day) -> Self {
+        let mut entry = Entry {
+            day,
+            golden: Golden::load(format!(
+                "{}/d{:02}.answers",
+                config::get().input_dir,
+                day.number
+            )),
+            answers: None,
+            duration: None,
+            output: Vec::new(),
+            running: false,
+        };
+
+        if let Some(cached) = day.implemented.then(|| cached_answers(day)).flatten() {
+            entry.answers = Some((cached.part1, cached.part2));
+            entry.duration = Some(Duration::from_micros(cached.duration_us));
+        }
+
+        entry
+    }
+
+    /// Whether the last answers match the golden ones, when both are known.
+    fn check(&self) -> Option<bool> {
+        let (part1, part2) = self.answers.as_ref()?;
+
+        Some(self.golden.as_ref()?.matches(part1, part2))
+    }
+
+    fn solved(&mut self, answers: Answers) {
+        self.output = match &self.golden {
+            Some(golden) => golden
+                .diff(&answers.part1, &answers.part2)
+                .into_iter()
+                .map(|l| match l {
+                    golden::Line::Same(l) => Line::raw(format!("  {}", l)),
+                    golden::Line::Expected(l) => {
+                        Line::styled(format!("- {}", l), Style::new().fg(Color::Red))
+                    }
+                    golden::Line::Got(l) => {
+                        Line::styled(format!("+ {}", l), Style::new().fg(Color::Green))
+                    }
+                })
+                .collect(),
+            None => vec![
+                Line::raw(format!("  Part 1: {}", answers.part1)),
+                Line::raw(format!("  Part 2: {}", answers.part2)),
+                Line::styled(
+                    format!(
+                        "No golden answers in {}/d{:02}.answers",
+                        config::get().input_dir,
+                        self.day.number
+                    ),
+                    Style::new().fg(Color::DarkGray),
+                ),
+            ],
+        };
+        self.output.push(Line::raw(format!(
+            "Duration: {}{}",
+            format_duration(answers.duration),
+            if answers.cached { " (cached)" } else { "" }
+        )));
+
+        self.answers = Some((answers.part1, answers.part2));
+        self.duration = Some(answers.duration);
+    }
+}
+
+impl Visualization {
+    fn title(&self) -> String {
+        format!(
+            " Day {} · part {} · step {}/{} ",
+            self.day.number,
+            self.part,
+            self.frame + 1,
+            self.frames.len()
+        )
+    }
+
+    fn step(&mut self, forward: bool) {
+        if forward {
+            self.frame = (self.frame + 1).min(self.frames.len().saturating_sub(1));
+        } else {
+            self.frame = self.frame.saturating_sub(1);
+        }
+    }
+}
+
+impl App {
+    fn new() -> Self {
+        let (tx, rx) = mpsc::channel();
+
+        App {
+            entries: DAYS.iter().map(Entry::new).collect(),
+            table: TableState::default().with_selected(0),
+            visualization: None,
+            status: String::from(HELP),
+            tx,
+            rx,
+        }
+    }
+
+    fn selected(&mut self) -> &mut Entry {
+        &mut self.entries[self.table.selected().unwrap_or(0)]
+    }
+
+    fn select(&mut self, offset: isize) {
+        let index = self.table.selected().unwrap_or(0) as isize + offset;
+        self.table.select(Some(
+            index.clamp(0, self.entries.len() as isize - 1) as usize
+        ));
+    }
+
+    /// Solves the selected day in the background, using the cache like `aoc run`.
+    fn run_day(&mut self) {
+        let index = self.table.selected().unwrap_or(0);
+        let entry = &mut self.entries[index];
+        if !entry.day.implemented || entry.running {
+            return;
+        }
+        entry.running = true;
+
+        let day = entry.day;
+        let tx = self.tx.clone();
+        thread::spawn(move || {
+            let path = day.input_path();
+            let res = match find_input(&day.input_name()) {
+                Ok(input) => {
+                    let cache = Cache::new(&config::get().cache_dir);
+                    crate::solve(day, &input, Some(&cache), None)
+                        .map_err(|e| e.with_file(&path).to_string())
+                }
+                Err(e) => Err(format!("Unable to read {}: {}", path, e)),
+            };
+            let _ = tx.send(Message::Solved(index, res));
+        });
+    }
+
+    /// Runs `cargo test` on the crate of the selected day in the background.
+    fn test_day(&mut self) {
+        let index = self.table.selected().unwrap_or(0);
+        let entry = &mut self.entries[index];
+        if !entry.day.implemented || entry.running {
+            return;
+        }
+        entry.running = true;
+
+        let package = format!("d{:02}", entry.day.number);
+        let tx = self.tx.clone();
+        thread::spawn(move || {
+            let message = match Command::new("cargo")
+                .args(["test", "--quiet", "--package", &package])
+                .output()
+            {
+                Ok(output) => Message::Tested(
+                    index,
+                    output.status.success(),
+                    String::from_utf8_lossy(&output.stdout).into_owned()
+                        + &String::from_utf8_lossy(&output.stderr),
+                ),
+                Err(e) => Message::Tested(index, false, format!("Unable to run cargo: {}", e)),
+            };
+            let _ = tx.send(message);
+        });
+    }
+
+    fn visualize(&mut self) {
+        let day = self.selected().day;
+        if day.visualize.is_none() {
+            self.status = format!("Day {} has no visualization", day.number);
+            return;
+        }
+
+        match find_input(&day.input_name()) {
+            Ok(input) => self.open_visualization(day, input, 1),
+            Err(e) => self.status = format!("Unable to read {}: {}", day.input_path(), e),
+        }
+    }
+
+    fn open_visualization(&mut self, day: &'static Day, input: Vec<String>, part: u8) {
+        match (day.visualize.unwrap())(&input, part) {
+            Ok(frames) => {
+                self.visualization = Some(Visualization {
+                    day,
+                    input,
+                    part,
+                    frames,
+                    frame: 0,
+                    playing: false,
+                });
+                self.status = String::from(VISUALIZATION_HELP);
+            }
+            Err(e) => {
+                let path = day.input_path();
+                self.selected().output =
+                    text_lines(&e.with_file(&path).to_string(), Style::new().fg(Color::Red));
+            }
+        }
+    }
+
+    fn handle(&mut self, message: Message) {
+        match message {
+            Message::Solved(index, res) => {
+                let entry = &mut self.entries[index];
+                entry.running = false;
+                match res {
+                    Ok(answers) => entry.solved(answers),
+                    Err(e) => entry.output = text_lines(&e, Style::new().fg(Color::Red)),
+                }
+            }
+            Message::Tested(index, success, output) => {
+                let entry = &mut self.entries[index];
+                entry.running = false;
+                entry.output = vec![if success {
+                    Line::styled("Tests passed", Style::new().fg(Color::Green))
+                } else {
+                    Line::styled("Tests failed", Style::new().fg(Color::Red))
+                }];
+                entry.output.extend(text_lines(&output, Style::new()));
+            }
+        }
+    }
+
+    /// Handles a key press, returning false to quit.
+    fn on_key(&mut self, code: KeyCode) -> bool {
+        if let Some(vis) = &mut self.visualization {
+            match code {
+                KeyCode::Esc | KeyCode::Char('q') => {
+                    self.visualization = None;
+                    self.status = String::from(HELP);
+                }
+                KeyCode::Right | KeyCode::Char('l') | KeyCode::Char(' ') => vis.step(true),
+                KeyCode::Left | KeyCode::Char('h') => vis.step(false),
+                KeyCode::Home => vis.frame = 0,
+                KeyCode::End => vis.frame = vis.frames.len().saturating_sub(1),
+                KeyCode::Char('a') => vis.playing = !vis.playing,
+                KeyCode::Char('p') => {
+                    let day = vis.day;
+                    let input = std::mem::take(&mut vis.input);
+                    let part = 3 - vis.part;
+                    self.open_visualization(day, input, part);
+                }
+                _ => {}
+            }
+
+            return true;
+        }
+
+        match code {
+            KeyCode::Esc | KeyCode::Char('q') => return false,
+            KeyCode::Up | KeyCode::Char('k') => self.select(-1),
+            KeyCode::Down | KeyCode::Char('j') => self.select(1),
+            KeyCode::Enter | KeyCode::Char('r') => self.run_day(),
+            KeyCode::Char('t') => self.test_day(),
+            KeyCode::Char('v') => self.visualize(),
+            _ => {}
+        }
+
+        true
+    }
+
+    fn on_tick(&mut self) {
+        while let Ok(message) = self.rx.try_recv() {
+            self.handle(message);
+        }
+
+        if let Some(vis) = &mut self.visualization {
+            if vis.playing {
+                vis.step(true);
+                vis.playing = vis.frame + 1 < vis.frames.len();
+            }
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [main, status] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+        frame.render_widget(
+            Paragraph::new(self.status.as_str()).style(Style::new().fg(Color::DarkGray)),
+            status,
+        );
+
+        if let Some(vis) = &self.visualization {
+            let lines = vis.frames.get(vis.frame).map_or(vec![], |grid| {
+                grid.iter()
+                    .map(|row| {
+                        Line::from(
+                            row.chars()
+                                .map(|c| match c {
+                                    '#' => Span::styled("#", Style::new().fg(Color::Yellow)),
+                                    '.' => Span::styled(".", Style::new().fg(Color::DarkGray)),
+                                    c => Span::raw(c.to_string()),
+                                })
+                                .collect::<Vec<Span>>(),
+                        )
+                    })
+                    .collect()
+            });
+            frame.render_widget(
+                Paragraph::new(lines).block(Block::bordered().title(vis.title())),
+                main,
+            );
+
+            return;
+        }
+
+        let [calendar, output] =
+            Layout::horizontal([Constraint::Length(66), Constraint::Min(0)]).areas(main);
+
+        let rows = self.entries.iter().map(|e| {
+            let status = if e.running {
+                "running"
+            } else if e.day.implemented {
+                "done"
+            } else {
+                "todo"
+            };
+            let (part1, part2) = e.answers.clone().unwrap_or_default();
+            let check = match e.check() {
+                Some(true) => Cell::from("✓").style(Style::new().fg(Color::Green)),
+                Some(false) => Cell::from("✗").style(Style::new().fg(Color::Red)),
+                None => Cell::from(""),
+            };
+
+            let row = Row::new(vec![
+                Cell::from(format!("{:02}", e.day.number)),
+                Cell::from(status),
+                Cell::from(part1),
+                Cell::from(part2),
+                Cell::from(e.duration.map(format_duration).unwrap_or_default()),
+                check,
+            ]);
+            if e.day.implemented {
+                row
+            } else {
+                row.style(Style::new().fg(Color::DarkGray))
+            }
+        });
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(3),
+                Constraint::Length(7),
+                Constraint::Length(15),
+                Constraint::Length(16),
+                Constraint::Length(12),
+                Constraint::Length(1),
+            ],
+        )
+        .header(
+            Row::new(vec!["Day", "Status", "Part 1", "Part 2", "Time", ""])
+                .style(Style::new().add_modifier(Modifier::BOLD)),
+        )
+        .row_highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+        .block(Block::bordered().title(" Advent of Code 2020 "));
+        frame.render_stateful_widget(table, calendar, &mut self.table);
+
+        let entry = &self.entries[self.table.selected().unwrap_or(0)];
+        frame.render_widget(
+            Paragraph::new(entry.output.clone())
+                .wrap(Wrap { trim: false })
+                .block(Block::bordered().title(format!(" Day {} ", entry.day.number))),
+            output,
+        );
+    }
+
+    fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        loop {
+            terminal.draw(|f| self.draw(f))?;
+
+            if event::poll(TICK)? {
+                if let Event::Key(key) = event::read()? {
+                    if key.kind == KeyEventKind::Press && !self.on_key(key.code) {
+                        return Ok(());
+                    }
+                }
+            }
+
+            self.on_tick();
+        }
+    }
+}
+
+/// Runs the dashboard until it's quit, restoring the terminal afterwards.
+pub fn run() -> io::Result<()> {
+    let mut terminal = ratatui::init();
+    let res = App::new().run(&mut terminal);
+    ratatui::restore();
+
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use ratatui::backend::TestBackend;
+    use ratatui::crossterm::event::KeyCode;
+    use ratatui::Terminal;
+
+    use crate::golden::Golden;
+    use crate::tui::{App, Message};
+    use crate::Answers;
+
+    fn screen(app: &mut App) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(120, 30)).unwrap();
+        terminal.draw(|f| app.draw(f)).unwrap();
+
+        terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|c| c.symbol())
+            .collect()
+    }
+
+    #[test]
+    fn test_solved() {
+        let mut app = App::new();
+        app.on_key(KeyCode::Down);
+        assert_eq!(Some(1), app.table.selected());
+
+        app.entries[1].golden = Some(Golden::parse("Part 1: 2\nPart 2: 1\n"));
+        app.handle(Message::Solved(
+            1,
+            Ok(Answers {
+                part1: String::from("2"),
+                part2: String::from("3"),
+                duration: Duration::from_millis(5),
+                cached: false,
+            }),
+        ));
+
+        assert_eq!(Some(false), app.entries[1].check());
+        let screen = screen(&mut app);
+        assert!(screen.contains("- Part 2: 1"), "{}", screen);
+        assert!(screen.contains("+ Part 2: 3"), "{}", screen);
+        assert!(screen.contains("5.000ms"), "{}", screen);
+    }
+
+    #[test]
+    fn test_visualization() {
+        let mut app = App::new();
+        app.open_visualization(crate::days::get(11).unwrap(), vec![String::from("L.L")], 1);
+
+        assert!(screen(&mut app).contains("step 1/2"));
+        app.on_key(KeyCode::Right);
+        app.on_key(KeyCode::Right);
+        assert!(screen(&mut app).contains("#.#"));
+
+        app.on_key(KeyCode::Esc);
+        assert!(app.visualization.is_none());
+        assert!(app.on_key(KeyCode::Char('k')));
+        assert!(!app.on_key(KeyCode::Char('q')));
+    }
+}
@@ -0,0 +1,82 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The `input_dir` of the configuration, as `aoc_2020::config` resolves it.
+fn input_dir(root: &Path) -> PathBuf {
+    println!("cargo:rerun-if-env-changed=AOC_CONFIG");
+    println!("cargo:rerun-if-env-changed=AOC_INPUT_DIR");
+
+    if let Ok(dir) = env::var("AOC_INPUT_DIR") {
+        return root.join(dir);
+    }
+
+    let config = root.join(env::var("AOC_CONFIG").unwrap_or_else(|_| String::from("aoc.toml")));
+    if config.is_file() {
+        println!("cargo:rerun-if-changed={}", config.display());
+    }
+
+    let dir = fs::read_to_string(&config)
+        .ok()
+        .and_then(|text| {
+            let table = text
+                .parse::<toml::Table>()
+                .unwrap_or_else(|e| panic!("Invalid configuration {}: {}", config.display(), e));
+            table.get("input_dir")?.as_str().map(String::from)
+        })
+        .unwrap_or_else(|| String::from("input"));
+
+    root.join(dir)
+}
+
+/// The files of `dir`, sorted, or none if it doesn't exist.
+fn list(dir: &Path) -> Vec<PathBuf> {
+    println!("cargo:rerun-if-changed={}", dir.display());
+
+    let mut files = fs::read_dir(dir)
+        .map(|entries| entries.map(|e| e.unwrap().path()).collect::<Vec<_>>())
+        .unwrap_or_default();
+    files.sort();
+
+    files
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    if env::var_os("CARGO_FEATURE_EMBED_INPUTS").is_none() {
+        return;
+    }
+
+    let input_dir = input_dir(Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()));
+
+    // Named inputs live one level down, in `dNN/`.
+    let mut files = Vec::new();
+    for path in list(&input_dir) {
+        if path.is_dir() {
+            files.extend(list(&path));
+        } else {
+            files.push(path);
+        }
+    }
+
+    let mut code = String::from("pub static INPUTS: &[(&str, &[u8])] = &[\n");
+    for path in files {
+        let file_name = path.file_name().unwrap().to_str().unwrap();
+        if path.is_file() && (file_name.ends_with(".txt") || file_name.ends_with(".txt.enc")) {
+            println!("cargo:rerun-if-changed={}", path.display());
+
+            // Inputs are looked up by their path in the input directory, e.g. `d09/alice.txt`.
+            let name = path.strip_prefix(&input_dir).unwrap().to_str().unwrap();
+            code.push_str(&format!(
+                "    ({:?}, include_bytes!({:?})),\n",
+                name.replace('\\', "/"),
+                path.canonicalize().unwrap()
+            ));
+        }
+    }
+    code.push_str("];\n");
+
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("embedded_inputs.rs");
+    fs::write(out, code).unwrap();
+}
@@ -0,0 +1,95 @@
+//! Day 1: Report Repair
+
+use std::fmt::Display;
+
+use itertools::Itertools;
+
+use aoc_2020::explain::{NoTrace, Step, Trace};
+use aoc_2020::{parse_ints, Diagnostic};
+
+/// Returns the product of the first `n` entries summing to 2020.
+pub fn find_product(input: &[i64], n: usize) -> Option<i64> {
+    find_product_traced(input, n, &mut NoTrace)
+}
+
+/// Same as `find_product`, tracing the entries found and their product as a step of part
+/// `n - 1`.
+pub fn find_product_traced(input: &[i64], n: usize, trace: &mut impl Trace) -> Option<i64> {
+    let values = input
+        .iter()
+        .combinations(n)
+        .find(|values| values.iter().copied().sum::<i64>() == 2020)?;
+    let product = values.iter().copied().product();
+
+    trace.step(|| {
+        Step::new(n as u8 - 1, "entries")
+            .with("entries", values.iter().join("+"))
+            .with("product", product)
+    });
+
+    Some(product)
+}
+
+/// Product of the two entries summing to 2020.
+pub fn part1(input: &[i64]) -> Option<i64> {
+    find_product(input, 2)
+}
+
+/// Product of the three entries summing to 2020.
+pub fn part2(input: &[i64]) -> Option<i64> {
+    find_product(input, 3)
+}
+
+/// Solves both parts of the puzzle.
+pub fn solve(input: &[String]) -> Result<(impl Display, impl Display), Diagnostic> {
+    let input = parse_ints(input)?;
+
+    let p1 = part1(&input).expect("No two entries sum to 2020");
+    let p2 = part2(&input).expect("No three entries sum to 2020");
+
+    Ok((p1, p2))
+}
+
+/// The steps of the solution.
+pub fn explain(input: &[String]) -> Result<Vec<Step>, Diagnostic> {
+    let input = parse_ints(input)?;
+    let mut steps = Vec::new();
+    find_product_traced(&input, 2, &mut steps);
+    find_product_traced(&input, 3, &mut steps);
+
+    Ok(steps)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{explain, part1, part2};
+
+    #[test]
+    fn test_p1() {
+        assert_eq!(Some(514579), part1(&[1721, 979, 366, 299, 675, 1456]));
+    }
+
+    #[test]
+    fn test_p2() {
+        assert_eq!(Some(241861950), part2(&[1721, 979, 366, 299, 675, 1456]));
+    }
+
+    #[test]
+    fn test_explain() {
+        let input = ["1721", "979", "366", "299", "675", "1456"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect::<Vec<String>>();
+        let steps = explain(&input).unwrap();
+
+        assert_eq!(2, steps.len());
+        assert_eq!(
+            "[part 1] entries entries=1721+299 product=514579",
+            steps[0].to_string()
+        );
+        assert_eq!(
+            "[part 2] entries entries=979+366+675 product=241861950",
+            steps[1].to_string()
+        );
+    }
+}
@@ -1,20 +1,18 @@
-use aoc_2020::get_input_as_int;
-use itertools::Itertools;
+use std::time::Instant;
+
+use aoc_2020::{exit_with_diagnostic, get_input};
+use d01::solve;
 
 fn main() {
-    let input = get_input_as_int("d01.txt");
+    let input = get_input("d01.txt");
+
+    let start = Instant::now();
+
+    let (r1, r2) = solve(input.as_slice()).unwrap_or_else(|e| exit_with_diagnostic(e, "d01.txt"));
 
-    for values in input.iter().combinations(2) {
-        if values.iter().map(|i| *i).sum::<i64>() == 2020 {
-            println!("Part 1: {}", values.into_iter().product::<i64>());
-            break;
-        }
-    }
+    let t = start.elapsed().as_micros() as f64 / 1000.0;
 
-    for values in input.iter().combinations(3) {
-        if values.iter().map(|i| *i).sum::<i64>() == 2020 {
-            println!("Part 2: {}", values.into_iter().product::<i64>());
-            break;
-        }
-    }
+    println!("Part 1: {}", r1);
+    println!("Part 2: {}", r2);
+    println!("Duration: {:.3}ms", t);
 }
@@ -0,0 +1,173 @@
+//! Day 2: Password Philosophy
+
+use std::fmt::Display;
+
+use aoc_2020::explain::{NoTrace, Step, Trace};
+use aoc_2020::parse::{letter, literal, parse_line, uint, word, Parser};
+use aoc_2020::Diagnostic;
+
+/// A password along with the policy it was created under.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Entry {
+    pub min: usize,
+    pub max: usize,
+    pub letter: char,
+    pub password: String,
+}
+
+impl Entry {
+    /// The letter must appear between `min` and `max` times.
+    pub fn is_valid_1(&self) -> bool {
+        let count = self.password.chars().filter(|c| c == &self.letter).count();
+
+        count >= self.min && count <= self.max
+    }
+
+    /// The letter must appear at exactly one of the (one-based) positions `min` and `max`.
+    pub fn is_valid_2(&self) -> bool {
+        let a = self.password.chars().nth(self.min - 1) == Some(self.letter);
+        let b = self.password.chars().nth(self.max - 1) == Some(self.letter);
+
+        a != b
+    }
+}
+
+/// Parses lines of the form `1-3 a: abcde`.
+pub fn parse(input: &[String]) -> Result<Vec<Entry>, Diagnostic> {
+    let position = || {
+        uint::<usize>().try_map(|n| {
+            if n > 0 {
+                Ok(n)
+            } else {
+                Err("positions start at 1")
+            }
+        })
+    };
+    let entry = (
+        position().skip(literal("-")),
+        position().skip(literal(" ")),
+        letter().skip(literal(": ")),
+        word(),
+    )
+        .map(|(min, max, letter, password)| Entry {
+            min,
+            max,
+            letter,
+            password,
+        });
+
+    input
+        .iter()
+        .enumerate()
+        .map(|(idx, line)| {
+            let e = parse_line(&entry, idx, line)?;
+            if e.min > e.max {
+                let max = line[..line.find(' ').unwrap()].split('-').nth(1).unwrap();
+                return Err(Diagnostic::at_token(
+                    idx,
+                    line,
+                    max,
+                    format!("expected at least {}", e.min),
+                ));
+            }
+
+            Ok(e)
+        })
+        .collect()
+}
+
+/// Number of passwords valid under the sled rental policy.
+pub fn part1(entries: &[Entry]) -> usize {
+    part1_traced(entries, &mut NoTrace)
+}
+
+/// Same as `part1`, tracing how often the letter of each entry appears in its password.
+pub fn part1_traced(entries: &[Entry], trace: &mut impl Trace) -> usize {
+    let mut valid = 0;
+
+    for (idx, e) in entries.iter().enumerate() {
+        let is_valid = e.is_valid_1();
+        if is_valid {
+            valid += 1;
+        }
+
+        trace.step(|| {
+            Step::new(1, "password")
+                .with("line", idx + 1)
+                .with("letter", e.letter)
+                .with(
+                    "count",
+                    e.password.chars().filter(|&c| c == e.letter).count(),
+                )
+                .with("valid", is_valid)
+                .with("total", valid)
+        });
+    }
+
+    valid
+}
+
+/// Number of passwords valid under the Official Toboggan Corporate Policy.
+pub fn part2(entries: &[Entry]) -> usize {
+    part2_traced(entries, &mut NoTrace)
+}
+
+/// Same as `part2`, tracing the letters at the two positions of each entry.
+pub fn part2_traced(entries: &[Entry], trace: &mut impl Trace) -> usize {
+    let mut valid = 0;
+
+    for (idx, e) in entries.iter().enumerate() {
+        let is_valid = e.is_valid_2();
+        if is_valid {
+            valid += 1;
+        }
+
+        trace.step(|| {
+            let at = |pos: usize| e.password.chars().nth(pos - 1).unwrap_or('-');
+            Step::new(2, "password")
+                .with("line", idx + 1)
+                .with("letter", e.letter)
+                .with("letters", format!("{}{}", at(e.min), at(e.max)))
+                .with("valid", is_valid)
+                .with("total", valid)
+        });
+    }
+
+    valid
+}
+
+/// Solves both parts of the puzzle.
+pub fn solve(input: &[String]) -> Result<(impl Display, impl Display), Diagnostic> {
+    let entries = parse(input)?;
+
+    Ok((part1(&entries), part2(&entries)))
+}
+
+/// The steps of the solution.
+pub fn explain(input: &[String]) -> Result<Vec<Step>, Diagnostic> {
+    let entries = parse(input)?;
+    let mut steps = Vec::new();
+    part1_traced(&entries, &mut steps);
+    part2_traced(&entries, &mut steps);
+
+    Ok(steps)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse;
+
+    #[test]
+    fn test_parse_error() {
+        let input = vec![String::from("1-3 a: abcde"), String::from("5-3 b: cdefg")];
+        let err = parse(&input).unwrap_err();
+        assert_eq!((2, 2..3), (err.line, err.columns));
+        assert_eq!("expected at least 5", err.message);
+
+        let err = parse(&[String::from("1-0 c: ccccc")]).unwrap_err();
+        assert_eq!((1, 2..3), (err.line, err.columns));
+        assert_eq!("positions start at 1", err.message);
+
+        assert!(parse(&[String::from("3-3 c: ccccc")]).is_ok());
+    }
+}
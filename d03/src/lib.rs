@@ -0,0 +1,134 @@
+//! Day 3: Toboggan Trajectory
+
+use std::fmt::Display;
+
+use aoc_2020::explain::{NoTrace, Step, Trace};
+use aoc_2020::grid::Grid;
+use aoc_2020::Diagnostic;
+
+/// Parses the map, `true` marking the trees.
+pub fn parse(input: &[String]) -> Result<Grid<bool>, Diagnostic> {
+    Grid::parse(input, |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })
+}
+
+/// Number of trees hit going down the slope `(right, down)`, the map repeating to the right.
+pub fn count_trees(map: &Grid<bool>, slope: (usize, usize)) -> usize {
+    let (x, y) = slope;
+
+    (0..map.height())
+        .step_by(y)
+        .enumerate()
+        .filter(|&(idx, row)| map.get_wrapping((row, idx * x)) == Some(&true))
+        .count()
+}
+
+/// Number of trees hit with a slope of right 3, down 1.
+pub fn part1(map: &Grid<bool>) -> usize {
+    part1_traced(map, &mut NoTrace)
+}
+
+/// Same as `part1`, tracing the trees hit.
+pub fn part1_traced(map: &Grid<bool>, trace: &mut impl Trace) -> usize {
+    let trees = count_trees(map, (3, 1));
+    trace.step(|| {
+        Step::new(1, "slope")
+            .with("right", 3)
+            .with("down", 1)
+            .with("trees", trees)
+    });
+
+    trees
+}
+
+/// Product of the number of trees hit on each of the five slopes.
+pub fn part2(map: &Grid<bool>) -> usize {
+    part2_traced(map, &mut NoTrace)
+}
+
+/// Same as `part2`, tracing the trees hit on each slope and the product so far.
+pub fn part2_traced(map: &Grid<bool>, trace: &mut impl Trace) -> usize {
+    let mut product = 1;
+
+    for &(right, down) in &[(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)] {
+        let trees = count_trees(map, (right, down));
+        product *= trees;
+
+        trace.step(|| {
+            Step::new(2, "slope")
+                .with("right", right)
+                .with("down", down)
+                .with("trees", trees)
+                .with("product", product)
+        });
+    }
+
+    product
+}
+
+/// Solves both parts of the puzzle.
+pub fn solve(input: &[String]) -> Result<(impl Display, impl Display), Diagnostic> {
+    let map = parse(input)?;
+
+    Ok((part1(&map), part2(&map)))
+}
+
+/// The steps of the solution.
+pub fn explain(input: &[String]) -> Result<Vec<Step>, Diagnostic> {
+    let map = parse(input)?;
+    let mut steps = Vec::new();
+    part1_traced(&map, &mut steps);
+    part2_traced(&map, &mut steps);
+
+    Ok(steps)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{explain, parse, part1, part2};
+
+    const INPUT: &str = "..##.......
+#...#...#..
+.#....#..#.
+..#.#...#.#
+.#...##..#.
+..#.##.....
+.#.#.#....#
+.#........#
+#.##...#...
+#...##....#
+.#..#...#.#";
+
+    #[test]
+    fn test_p1() {
+        let input = INPUT.split('\n').map(String::from).collect::<Vec<String>>();
+
+        assert_eq!(7, part1(&parse(&input).unwrap()));
+    }
+
+    #[test]
+    fn test_p2() {
+        let input = INPUT.split('\n').map(String::from).collect::<Vec<String>>();
+
+        assert_eq!(336, part2(&parse(&input).unwrap()));
+    }
+
+    #[test]
+    fn test_explain() {
+        let input = INPUT.split('\n').map(String::from).collect::<Vec<String>>();
+        let steps = explain(&input).unwrap();
+
+        assert_eq!(6, steps.len());
+        assert_eq!(
+            "[part 1] slope right=3 down=1 trees=7",
+            steps[0].to_string()
+        );
+        assert_eq!(
+            "[part 2] slope right=1 down=2 trees=2 product=336",
+            steps[5].to_string()
+        );
+    }
+}
@@ -0,0 +1,326 @@
+//! Day 4: Passport Processing
+
+use std::fmt::Display;
+use std::ops::RangeInclusive;
+
+use aoc_2020::explain::{NoTrace, Step, Trace};
+use aoc_2020::parse::{literal, one_of, parse_line, parse_str, separated, take_while1, uint};
+use aoc_2020::Diagnostic;
+
+const KEYS: &[&str] = &["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid", "cid"];
+const VALID_ECL: &[&str] = &["amb", "blu", "brn", "gry", "grn", "hzl", "oth"];
+
+/// A passport or North Pole credentials, with the fields found in the batch file.
+#[derive(Debug, Default)]
+pub struct Passport {
+    pub byr: Option<String>,
+    pub iyr: Option<String>,
+    pub eyr: Option<String>,
+    pub hgt: Option<String>,
+    pub hcl: Option<String>,
+    pub ecl: Option<String>,
+    pub pid: Option<String>,
+    pub cid: Option<String>,
+}
+
+impl Passport {
+    /// The first required field that is missing, if any. `cid` isn't required.
+    pub fn missing_field(&self) -> Option<&'static str> {
+        let fields = [
+            ("byr", &self.byr),
+            ("iyr", &self.iyr),
+            ("eyr", &self.eyr),
+            ("hgt", &self.hgt),
+            ("hcl", &self.hcl),
+            ("ecl", &self.ecl),
+            ("pid", &self.pid),
+        ];
+
+        fields
+            .iter()
+            .find(|(_, value)| value.is_none())
+            .map(|&(key, _)| key)
+    }
+
+    /// Whether all fields but `cid` are present.
+    pub fn has_all_fields(&self) -> bool {
+        self.missing_field().is_none()
+    }
+
+    /// The first required field that is missing or holds an invalid value, if any.
+    pub fn invalid_field(&self) -> Option<&'static str> {
+        if let Some(key) = self.missing_field() {
+            return Some(key);
+        }
+
+        let year = |value: &str, range: RangeInclusive<i32>| {
+            value.len() == 4 && value.parse().is_ok_and(|y| range.contains(&y))
+        };
+
+        if !year(self.byr.as_ref().unwrap(), 1920..=2002) {
+            return Some("byr");
+        }
+        if !year(self.iyr.as_ref().unwrap(), 2010..=2020) {
+            return Some("iyr");
+        }
+        if !year(self.eyr.as_ref().unwrap(), 2020..=2030) {
+            return Some("eyr");
+        }
+
+        let hgt = (uint::<u32>(), one_of(&["cm", "in"]));
+        match parse_str(&hgt, self.hgt.as_ref().unwrap()) {
+            Ok((h, "cm")) if (150..=193).contains(&h) => {}
+            Ok((h, "in")) if (59..=76).contains(&h) => {}
+            _ => return Some("hgt"),
+        }
+
+        let hcl = (
+            literal("#"),
+            take_while1(|c| matches!(c, '0'..='9' | 'a'..='f'), "a hex digit"),
+        );
+        match parse_str(&hcl, self.hcl.as_ref().unwrap()) {
+            Ok((_, digits)) if digits.len() == 6 => {}
+            _ => return Some("hcl"),
+        }
+
+        if !VALID_ECL.contains(&self.ecl.as_ref().unwrap().as_str()) {
+            return Some("ecl");
+        }
+
+        let pid = self.pid.as_ref().unwrap();
+        if pid.len() != 9 || pid.parse::<i32>().is_err() {
+            return Some("pid");
+        }
+
+        None
+    }
+
+    /// Whether all required fields are present and hold valid values.
+    pub fn is_valid(&self) -> bool {
+        self.invalid_field().is_none()
+    }
+}
+
+/// Parses the batch file, where passports are separated by blank lines.
+pub fn parse_passports(lines: &[String]) -> Result<Vec<Passport>, Diagnostic> {
+    let mut passports: Vec<Passport> = Vec::new();
+
+    let mut byr: Option<String> = None;
+    let mut iyr: Option<String> = None;
+    let mut eyr: Option<String> = None;
+    let mut hgt: Option<String> = None;
+    let mut hcl: Option<String> = None;
+    let mut ecl: Option<String> = None;
+    let mut pid: Option<String> = None;
+    let mut cid: Option<String> = None;
+
+    let field = (
+        one_of(KEYS),
+        literal(":"),
+        take_while1(|c| c != ' ', "a value"),
+    );
+    let fields = separated(field, literal(" "));
+
+    for (idx, line) in lines.iter().enumerate() {
+        if line.is_empty() {
+            let pp = Passport {
+                byr: byr.clone(),
+                iyr: iyr.clone(),
+                eyr: eyr.clone(),
+                hgt: hgt.clone(),
+                hcl: hcl.clone(),
+                ecl: ecl.clone(),
+                pid: pid.clone(),
+                cid: cid.clone(),
+            };
+
+            passports.push(pp);
+
+            byr = None;
+            iyr = None;
+            eyr = None;
+            hgt = None;
+            hcl = None;
+            ecl = None;
+            pid = None;
+            cid = None;
+        } else {
+            for (k, _, v) in parse_line(&fields, idx, line)? {
+                match k {
+                    "byr" => byr = Some(v),
+                    "iyr" => iyr = Some(v),
+                    "eyr" => eyr = Some(v),
+                    "hgt" => hgt = Some(v),
+                    "hcl" => hcl = Some(v),
+                    "ecl" => ecl = Some(v),
+                    "pid" => pid = Some(v),
+                    _ => cid = Some(v),
+                }
+            }
+        }
+    }
+
+    let pp = Passport {
+        byr: byr.clone(),
+        iyr: iyr.clone(),
+        eyr: eyr.clone(),
+        hgt: hgt.clone(),
+        hcl: hcl.clone(),
+        ecl: ecl.clone(),
+        pid: pid.clone(),
+        cid: cid.clone(),
+    };
+
+    passports.push(pp);
+
+    Ok(passports)
+}
+
+/// Number of passports with all the required fields.
+pub fn part1(passports: &[Passport]) -> usize {
+    part1_traced(passports, &mut NoTrace)
+}
+
+/// Same as `part1`, tracing the first missing field of each passport.
+pub fn part1_traced(passports: &[Passport], trace: &mut impl Trace) -> usize {
+    count_valid(passports, 1, Passport::missing_field, trace)
+}
+
+/// Number of passports with all the required fields holding valid values.
+pub fn part2(passports: &[Passport]) -> usize {
+    part2_traced(passports, &mut NoTrace)
+}
+
+/// Same as `part2`, tracing the first invalid field of each passport.
+pub fn part2_traced(passports: &[Passport], trace: &mut impl Trace) -> usize {
+    count_valid(passports, 2, Passport::invalid_field, trace)
+}
+
+/// Number of passports for which `reject` finds no field, tracing its finding for each one.
+fn count_valid(
+    passports: &[Passport],
+    part: u8,
+    reject: impl Fn(&Passport) -> Option<&'static str>,
+    trace: &mut impl Trace,
+) -> usize {
+    let mut valid = 0;
+
+    for (idx, pp) in passports.iter().enumerate() {
+        let rejected = reject(pp);
+        if rejected.is_none() {
+            valid += 1;
+        }
+
+        trace.step(|| {
+            Step::new(part, "passport")
+                .with("index", idx)
+                .with("rejected", rejected.unwrap_or("none"))
+                .with("valid", valid)
+        });
+    }
+
+    valid
+}
+
+/// Solves both parts of the puzzle.
+pub fn solve(input: &[String]) -> Result<(impl Display, impl Display), Diagnostic> {
+    let passports = parse_passports(input)?;
+
+    Ok((part1(&passports), part2(&passports)))
+}
+
+/// The steps of the solution.
+pub fn explain(input: &[String]) -> Result<Vec<Step>, Diagnostic> {
+    let passports = parse_passports(input)?;
+    let mut steps = Vec::new();
+    part1_traced(&passports, &mut steps);
+    part2_traced(&passports, &mut steps);
+
+    Ok(steps)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parse_passports, part1, part2};
+
+    #[test]
+    fn test_p1() {
+        let input = "ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
+byr:1937 iyr:2017 cid:147 hgt:183cm
+
+iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884
+hcl:#cfa07d byr:1929
+
+hcl:#ae17e1 iyr:2013
+eyr:2024
+ecl:brn pid:760753108 byr:1931
+hgt:179cm
+
+hcl:#cfa07d eyr:2025 pid:166559648
+iyr:2011 ecl:brn hgt:59in";
+
+        let input = input.split('\n').map(String::from).collect::<Vec<String>>();
+        let res = part1(&parse_passports(&input).unwrap());
+
+        assert_eq!(2, res);
+    }
+
+    #[test]
+    fn test_p2() {
+        let invalid = "eyr:1972 cid:100
+hcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926
+
+iyr:2019
+hcl:#602927 eyr:1967 hgt:170cm
+ecl:grn pid:012533040 byr:1946
+
+hcl:dab227 iyr:2012
+ecl:brn hgt:182cm pid:021572410 eyr:2020 byr:1992 cid:277
+
+hgt:59cm ecl:zzz
+eyr:2038 hcl:74454a iyr:2023
+pid:3556412378 byr:2007";
+
+        let input = invalid
+            .split('\n')
+            .map(String::from)
+            .collect::<Vec<String>>();
+        let passports = parse_passports(&input).unwrap();
+        assert_eq!((4, 0), (passports.len(), part2(&passports)));
+
+        let valid = "pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980
+hcl:#623a2f
+
+eyr:2029 ecl:blu cid:129 byr:1989
+iyr:2014 pid:896056539 hcl:#a97842 hgt:165cm
+
+hcl:#888785
+hgt:164cm byr:2001 iyr:2015 cid:88
+pid:545766238 ecl:hzl
+eyr:2022
+
+iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719";
+
+        let input = valid.split('\n').map(String::from).collect::<Vec<String>>();
+        let passports = parse_passports(&input).unwrap();
+        assert_eq!((4, 4), (passports.len(), part2(&passports)));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = "ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
+
+iyr:2013 ecl:amb xyz:350 eyr:2023";
+
+        let input = input.split('\n').map(String::from).collect::<Vec<String>>();
+        let err = parse_passports(&input).unwrap_err();
+
+        assert_eq!(3, err.line);
+        assert_eq!(17..20, err.columns);
+        assert!(
+            err.message.starts_with("unexpected `xyz`"),
+            "{}",
+            err.message
+        );
+    }
+}
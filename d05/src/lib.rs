@@ -0,0 +1,202 @@
+//! Day 5: Binary Boarding
+
+use std::fmt::Display;
+
+use aoc_2020::explain::{NoTrace, Step, Trace};
+use aoc_2020::parse::{parse_lines, take_while1, Parser};
+use aoc_2020::Diagnostic;
+
+/// Decodes a boarding pass such as `FBFBBFFRLR` into its seat id, `row * 8 + column`. Characters
+/// other than `F`, `B`, `L` and `R` are ignored.
+pub fn get_seat_id(pass: &str) -> i32 {
+    let mut min_row = 0;
+    let mut max_row = 127;
+    let mut min_col = 0;
+    let mut max_col = 7;
+
+    for c in pass.chars() {
+        match c {
+            'F' => max_row -= (max_row + 1 - min_row) / 2,
+            'B' => min_row += (max_row + 1 - min_row) / 2,
+            'L' => max_col -= (max_col + 1 - min_col) / 2,
+            'R' => min_col += (max_col + 1 - min_col) / 2,
+            _ => {}
+        }
+    }
+
+    min_row * 8 + min_col
+}
+
+/// Encodes a seat id into its boarding pass, the inverse of `get_seat_id`.
+pub fn get_boarding_pass(seat_id: i32) -> String {
+    (0..10)
+        .rev()
+        .map(|bit| {
+            let set = seat_id & (1 << bit) != 0;
+            match (bit >= 3, set) {
+                (true, false) => 'F',
+                (true, true) => 'B',
+                (false, false) => 'L',
+                (false, true) => 'R',
+            }
+        })
+        .collect()
+}
+
+/// Parses the boarding passes into their seat ids.
+pub fn parse(input: &[String]) -> Result<Vec<i32>, Diagnostic> {
+    let row = take_while1(|c| c == 'F' || c == 'B', "`F` or `B`").try_map(|r| {
+        if r.len() == 7 {
+            Ok(r)
+        } else {
+            Err(format!("expected 7 row characters, found {}", r.len()))
+        }
+    });
+    let col = take_while1(|c| c == 'L' || c == 'R', "`L` or `R`").try_map(|c| {
+        if c.len() == 3 {
+            Ok(c)
+        } else {
+            Err(format!("expected 3 column characters, found {}", c.len()))
+        }
+    });
+    let pass = (row, col).map(|(row, col)| get_seat_id(&(row + &col)));
+
+    if input.is_empty() {
+        return Err(Diagnostic::new(0, "", 0..0, "expected a boarding pass"));
+    }
+
+    parse_lines(&pass, input)
+}
+
+/// Highest seat id.
+pub fn part1(seat_ids: &[i32]) -> i32 {
+    part1_traced(seat_ids, &mut NoTrace)
+}
+
+/// Same as `part1`, tracing the row and column of each seat and the highest id so far.
+pub fn part1_traced(seat_ids: &[i32], trace: &mut impl Trace) -> i32 {
+    let mut highest = 0;
+
+    for &id in seat_ids {
+        highest = highest.max(id);
+
+        trace.step(|| {
+            Step::new(1, "seat")
+                .with("row", id / 8)
+                .with("column", id % 8)
+                .with("id", id)
+                .with("highest", highest)
+        });
+    }
+
+    highest
+}
+
+/// The missing seat id, whose neighbours are taken.
+pub fn part2(seat_ids: &[i32]) -> i32 {
+    part2_traced(seat_ids, &mut NoTrace)
+}
+
+/// Same as `part2`, tracing the two seats around the gap.
+pub fn part2_traced(seat_ids: &[i32], trace: &mut impl Trace) -> i32 {
+    let mut seat_ids = seat_ids.to_vec();
+    seat_ids.sort_unstable();
+
+    let mut prev = seat_ids[0];
+    for s in seat_ids {
+        if s - prev == 2 {
+            trace.step(|| {
+                Step::new(2, "gap")
+                    .with("before", prev)
+                    .with("after", s)
+                    .with("seat", s - 1)
+            });
+
+            return s - 1;
+        }
+        prev = s;
+    }
+
+    0
+}
+
+/// Solves both parts of the puzzle.
+pub fn solve(input: &[String]) -> Result<(impl Display, impl Display), Diagnostic> {
+    let seat_ids = parse(input)?;
+
+    Ok((part1(&seat_ids), part2(&seat_ids)))
+}
+
+/// The steps of the solution.
+pub fn explain(input: &[String]) -> Result<Vec<Step>, Diagnostic> {
+    let seat_ids = parse(input)?;
+    let mut steps = Vec::new();
+    part1_traced(&seat_ids, &mut steps);
+    part2_traced(&seat_ids, &mut steps);
+
+    Ok(steps)
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::{explain, get_boarding_pass, get_seat_id, parse};
+
+    #[test]
+    fn test_get_seat_id() {
+        assert_eq!(357, get_seat_id(&String::from("FBFBBFFRLR")))
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = vec![String::from("BFFFBBFRRR"), String::from("FFFBBBFRXR")];
+        let err = parse(&input).unwrap_err();
+        assert_eq!((2, 7..8), (err.line, err.columns));
+
+        let err = parse(&[String::from("FBFBBFRLR")]).unwrap_err();
+        assert_eq!((1, 0..6), (err.line, err.columns));
+        assert_eq!("expected 7 row characters, found 6", err.message);
+
+        assert!(parse(&[String::from("FBFBBFFRLRL")]).is_err());
+        assert!(parse(&[]).is_err());
+    }
+
+    #[test]
+    fn test_explain() {
+        let input = ["BFFFBBFRRR", "FFFBBBFRRR", "BFFFBBFRLR", "BBFFBBFRLL"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect::<Vec<String>>();
+        let steps = explain(&input).unwrap();
+
+        assert_eq!(5, steps.len());
+        assert_eq!(
+            "[part 1] seat row=70 column=7 id=567 highest=567",
+            steps[0].to_string()
+        );
+        assert_eq!(Some("820"), steps[3].get("highest"));
+        assert_eq!(
+            "[part 2] gap before=565 after=567 seat=566",
+            steps[4].to_string()
+        );
+    }
+
+    #[test]
+    fn test_get_boarding_pass() {
+        assert_eq!("FBFBBFFRLR", get_boarding_pass(357));
+        assert_eq!("BBFFBBFRLL", get_boarding_pass(820));
+    }
+
+    proptest! {
+        #[test]
+        fn prop_seat_id_round_trip(seat_id in 0..1024) {
+            prop_assert_eq!(seat_id, get_seat_id(&get_boarding_pass(seat_id)));
+        }
+
+        #[test]
+        fn prop_boarding_pass_round_trip(pass in "[FB]{7}[LR]{3}") {
+            prop_assert_eq!(&pass, &get_boarding_pass(get_seat_id(&pass)));
+        }
+    }
+}
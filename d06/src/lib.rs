@@ -0,0 +1,179 @@
+//! Day 6: Custom Customs
+
+use std::fmt::Display;
+
+use aoc_2020::bitset::{letter_index, SmallBitSet};
+use aoc_2020::explain::{Step, Trace};
+use aoc_2020::Diagnostic;
+
+/// Parses the questions answered by each person, grouped, one group per paragraph.
+fn parse_people(lines: &[String]) -> Result<Vec<Vec<SmallBitSet>>, Diagnostic> {
+    let mut groups = vec![Vec::new()];
+
+    for (idx, l) in lines.iter().enumerate() {
+        if l.is_empty() {
+            groups.push(Vec::new());
+            continue;
+        }
+
+        let person = SmallBitSet::from_letters(l).ok_or_else(|| {
+            let (col, c) = l
+                .char_indices()
+                .find(|&(_, c)| letter_index(c).is_none())
+                .unwrap();
+            Diagnostic::new(
+                idx,
+                l,
+                col..col + c.len_utf8(),
+                format!("invalid answer: '{}'", c),
+            )
+        })?;
+        groups.last_mut().unwrap().push(person);
+    }
+
+    Ok(groups)
+}
+
+/// Parses the questions answered by anyone in each group.
+pub fn parse_groups_1(lines: &[String]) -> Result<Vec<SmallBitSet>, Diagnostic> {
+    Ok(parse_people(lines)?
+        .iter()
+        .map(|g| g.iter().fold(SmallBitSet::new(), |a, &p| a | p))
+        .collect())
+}
+
+/// Parses the questions answered by everyone in each group.
+pub fn parse_groups_2(lines: &[String]) -> Result<Vec<SmallBitSet>, Diagnostic> {
+    Ok(parse_people(lines)?
+        .iter()
+        .map(|g| g.iter().copied().reduce(|a, p| a & p).unwrap_or_default())
+        .collect())
+}
+
+/// Sum of the number of questions of each group.
+pub fn count_questions(groups: &[SmallBitSet]) -> usize {
+    groups.iter().map(|g| g.len()).sum()
+}
+
+/// Same as `count_questions`, tracing the questions of each group as steps of `part`.
+pub fn count_questions_traced(groups: &[SmallBitSet], part: u8, trace: &mut impl Trace) -> usize {
+    let mut total = 0;
+
+    for (idx, g) in groups.iter().enumerate() {
+        total += g.len();
+
+        trace.step(|| {
+            Step::new(part, "group")
+                .with("index", idx)
+                .with("questions", g.letters().collect::<String>())
+                .with("total", total)
+        });
+    }
+
+    total
+}
+
+/// Solves both parts of the puzzle.
+pub fn solve(input: &[String]) -> Result<(impl Display, impl Display), Diagnostic> {
+    let p1 = count_questions(&parse_groups_1(input)?);
+    let p2 = count_questions(&parse_groups_2(input)?);
+
+    Ok((p1, p2))
+}
+
+/// The steps of the solution.
+pub fn explain(input: &[String]) -> Result<Vec<Step>, Diagnostic> {
+    let mut steps = Vec::new();
+    count_questions_traced(&parse_groups_1(input)?, 1, &mut steps);
+    count_questions_traced(&parse_groups_2(input)?, 2, &mut steps);
+
+    Ok(steps)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{explain, parse_groups_1, parse_groups_2};
+
+    #[test]
+    fn test_parse_groups_1() {
+        let input = "abc
+
+a
+b
+c
+
+ab
+ac
+
+a
+a
+a
+a
+
+b";
+
+        let groups =
+            parse_groups_1(&input.split("\n").map(String::from).collect::<Vec<String>>()).unwrap();
+        println!("Groups: {:?}", groups);
+        assert_eq!(11_usize, groups.iter().map(|g| g.len()).sum())
+    }
+
+    #[test]
+    fn test_parse_groups_2() {
+        let input = "abc
+
+a
+b
+c
+
+ab
+ac
+
+a
+a
+a
+a
+
+b";
+
+        let groups =
+            parse_groups_2(&input.split("\n").map(String::from).collect::<Vec<String>>()).unwrap();
+        println!("Groups: {:?}", groups);
+        assert_eq!(6_usize, groups.iter().map(|g| g.len()).sum())
+    }
+
+    #[test]
+    fn test_explain() {
+        let input = ["abc", "", "ab", "ac"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect::<Vec<String>>();
+        let steps = explain(&input)
+            .unwrap()
+            .iter()
+            .map(|s| s.to_string())
+            .collect::<Vec<String>>();
+
+        assert_eq!(
+            vec![
+                "[part 1] group index=0 questions=abc total=3",
+                "[part 1] group index=1 questions=abc total=6",
+                "[part 2] group index=0 questions=abc total=3",
+                "[part 2] group index=1 questions=a total=4",
+            ],
+            steps
+        );
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = ["ab", "", "aB"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect::<Vec<String>>();
+        let err = parse_groups_1(&input).unwrap_err();
+
+        assert_eq!((3, 1..2), (err.line, err.columns));
+        assert_eq!("invalid answer: 'B'", err.message);
+    }
+}
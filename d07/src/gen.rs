@@ -0,0 +1,124 @@
+//! Random inputs for day 7.
+//!
+//! Bags are numbered and only contain bags with a higher number, so the rules always form a
+//! DAG. The bags before shiny gold are split between those that are made to reach it and those
+//! that can't, which plants the answer of part 1; part 2 is counted while building the rules.
+
+use std::collections::HashSet;
+
+use aoc_2020::gen::{rng, word, GenRng, Generated, Rng, SliceRandom};
+
+/// Upper bound on the number of bags inside any bag, well within the `u32` of part 2.
+const MAX_CONTENTS: u64 = 100_000_000;
+
+/// `n - 1` distinct random colors, shiny gold being the last one.
+fn colors(rng: &mut GenRng, n: usize) -> Vec<String> {
+    let mut seen = HashSet::new();
+    seen.insert(String::from("shiny gold"));
+
+    while seen.len() < n {
+        seen.insert(format!("{} {}", word(rng, 2), word(rng, 2)));
+    }
+    seen.remove("shiny gold");
+
+    let mut colors = seen.into_iter().collect::<Vec<String>>();
+    colors.sort();
+    colors.shuffle(rng);
+
+    colors
+}
+
+/// Picks a few bags after `idx`, mostly close to it so that the nesting is deep.
+fn pick_children(rng: &mut GenRng, idx: usize, candidates: &[usize]) -> Vec<usize> {
+    let after = candidates
+        .iter()
+        .copied()
+        .filter(|&j| j > idx)
+        .take(8)
+        .collect::<Vec<usize>>();
+
+    let n = rng.gen_range(0..=3.min(after.len()));
+    after.choose_multiple(rng, n).copied().collect()
+}
+
+/// Generates the rules of `size` bag colors, shiny gold included.
+pub fn generate(size: usize, seed: u64) -> Generated {
+    let mut rng = rng(seed);
+    let size = size.max(2);
+
+    let gold = rng.gen_range(size / 3..size);
+    let mut names = colors(&mut rng, size);
+    names.insert(gold, String::from("shiny gold"));
+
+    let reaches_gold = (0..size)
+        .map(|i| i < gold && rng.gen_bool(0.4))
+        .collect::<Vec<bool>>();
+    let others = (0..size)
+        .filter(|&i| i != gold && !reaches_gold[i])
+        .collect::<Vec<usize>>();
+
+    let mut contents: Vec<Vec<(u64, usize)>> = vec![Vec::new(); size];
+    let mut totals = vec![0u64; size];
+
+    for i in (0..size).rev() {
+        let mut children = Vec::new();
+        let mut total = 0u64;
+
+        if reaches_gold[i] {
+            let next = (i + 1..gold).find(|&j| reaches_gold[j]).unwrap_or(gold);
+            let amount = rng.gen_range(1..=3);
+            children.push((amount, next));
+            total = totals[next].saturating_add(1).saturating_mul(amount);
+        }
+
+        for j in pick_children(&mut rng, i, &others) {
+            let amount = (1..=rng.gen_range(1..=4))
+                .rev()
+                .find(|a| total.saturating_add(a * (1 + totals[j])) <= MAX_CONTENTS);
+            if let Some(amount) = amount {
+                children.push((amount, j));
+                total += amount * (1 + totals[j]);
+            }
+        }
+
+        totals[i] = total;
+        contents[i] = children;
+    }
+
+    let mut input = (0..size)
+        .map(|i| {
+            if contents[i].is_empty() {
+                return format!("{} bags contain no other bags.", names[i]);
+            }
+
+            let contents = contents[i]
+                .iter()
+                .map(|(a, j)| format!("{} {} bag{}", a, names[*j], if *a > 1 { "s" } else { "" }))
+                .collect::<Vec<String>>();
+            format!("{} bags contain {}.", names[i], contents.join(", "))
+        })
+        .collect::<Vec<String>>();
+    input.shuffle(&mut rng);
+
+    let part1 = reaches_gold.iter().filter(|&&r| r).count();
+
+    Generated::with_answers(input, part1, totals[gold])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::gen::generate;
+    use crate::solve;
+
+    #[test]
+    fn test_generate() {
+        for seed in 0..10 {
+            let gen = generate(40, seed);
+            let (p1, p2) = solve(&gen.input).unwrap();
+
+            assert_eq!(40, gen.input.len());
+            assert_eq!(gen.part1, Some(p1.to_string()));
+            assert_eq!(gen.part2, Some(p2.to_string()));
+        }
+    }
+}
@@ -0,0 +1,268 @@
+//! Day 7: Handy Haversacks
+
+use std::fmt::Display;
+
+use aoc_2020::explain::{NoTrace, Step, Trace};
+use aoc_2020::graph::{Cycle, Graph, NodeId};
+use aoc_2020::parse::{literal, optional, parse_line, separated, uint, word, Parser};
+use aoc_2020::Diagnostic;
+
+pub mod gen;
+
+/// The bag rules as a graph of colors, with an edge weighted by the amount of bags from each
+/// color to the ones it must contain.
+pub type Bags = Graph<String, u32>;
+
+/// A color of two words, like `shiny gold`.
+fn color() -> impl Parser<String> {
+    (word(), literal(" "), word()).map(|(adj, _, hue)| format!("{} {}", adj, hue))
+}
+
+/// Parses the bag rules.
+pub fn parse(input: &[String]) -> Result<Bags, Diagnostic> {
+    let content = (
+        uint::<u32>(),
+        literal(" "),
+        color(),
+        literal(" bag"),
+        optional(literal("s")),
+    )
+        .map(|(amount, _, inner, _, _)| (amount, inner));
+    let contents = literal("no other bags")
+        .map(|_| Vec::new())
+        .or(separated(content, literal(", ")));
+    let rule = (color(), literal(" bags contain "), contents, literal("."));
+
+    let mut bags = Graph::new();
+
+    for (idx, l) in input.iter().enumerate() {
+        let (color, _, contents, _) = parse_line(&rule, idx, l)?;
+        bags.node(color.clone());
+
+        for (amount, inner) in contents {
+            bags.add_edge(color.clone(), inner, amount);
+        }
+    }
+
+    Ok(bags)
+}
+
+/// Number of bag colors that can eventually contain a shiny gold bag.
+pub fn part1(bags: &Bags) -> usize {
+    part1_traced(bags, &mut NoTrace)
+}
+
+/// Same as `part1`, tracing each color that can contain a shiny gold bag.
+pub fn part1_traced(bags: &Bags, trace: &mut impl Trace) -> usize {
+    let ancestors = match bags.id("shiny gold") {
+        Some(gold) => bags.ancestors(gold),
+        None => return 0,
+    };
+
+    for (idx, &id) in ancestors.iter().enumerate() {
+        trace.step(|| {
+            Step::new(1, "container")
+                .with("color", bags.get(id))
+                .with("count", idx + 1)
+        });
+    }
+
+    ancestors.len()
+}
+
+/// Total number of bags inside the bag `id`, unless one of them can contain itself.
+pub fn count_contents(bags: &Bags, id: NodeId) -> Result<u32, Cycle> {
+    count_contents_traced(bags, id, &mut NoTrace)
+}
+
+/// Same as `count_contents`, tracing the number of bags inside each bag it goes through,
+/// innermost first.
+pub fn count_contents_traced(
+    bags: &Bags,
+    id: NodeId,
+    trace: &mut impl Trace,
+) -> Result<u32, Cycle> {
+    bags.fold(id, |id, contents| {
+        let count = contents
+            .iter()
+            .map(|&(amount, count)| amount + amount * count)
+            .sum();
+
+        trace.step(|| {
+            Step::new(2, "contents")
+                .with("color", bags.get(id))
+                .with("bags", count)
+        });
+
+        count
+    })
+}
+
+/// Number of bags required inside a shiny gold bag, unless one of them can contain itself.
+pub fn part2(bags: &Bags) -> Result<u32, Cycle> {
+    part2_traced(bags, &mut NoTrace)
+}
+
+/// Same as `part2`, tracing the contents of each bag inside the shiny gold one.
+pub fn part2_traced(bags: &Bags, trace: &mut impl Trace) -> Result<u32, Cycle> {
+    match bags.id("shiny gold") {
+        Some(gold) => count_contents_traced(bags, gold, trace),
+        None => Ok(0),
+    }
+}
+
+/// Points at the rule of the first color of `cycle`.
+fn cyclic(input: &[String], bags: &Bags, cycle: Cycle) -> Diagnostic {
+    let colors = cycle
+        .nodes
+        .iter()
+        .chain(cycle.nodes.first())
+        .map(|&id| bags.get(id).as_str())
+        .collect::<Vec<&str>>();
+    let message = format!("bags can contain themselves: {}", colors.join(" -> "));
+    let rule = format!("{} bags contain ", colors[0]);
+
+    match input.iter().position(|l| l.starts_with(&rule)) {
+        Some(idx) => {
+            Diagnostic::at_token(idx, &input[idx], &input[idx][..colors[0].len()], message)
+        }
+        None => Diagnostic::new(input.len(), "", 0..0, message),
+    }
+}
+
+/// Solves both parts of the puzzle.
+pub fn solve(input: &[String]) -> Result<(impl Display, impl Display), Diagnostic> {
+    let bags = parse(input)?;
+
+    let p1 = part1(&bags);
+    let p2 = part2(&bags).map_err(|e| cyclic(input, &bags, e))?;
+
+    Ok((p1, p2))
+}
+
+/// The steps of the solution.
+pub fn explain(input: &[String]) -> Result<Vec<Step>, Diagnostic> {
+    let bags = parse(input)?;
+    let mut steps = Vec::new();
+    part1_traced(&bags, &mut steps);
+    part2_traced(&bags, &mut steps).map_err(|e| cyclic(input, &bags, e))?;
+
+    Ok(steps)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{explain, parse, part1, part2, solve};
+
+    #[test]
+    fn test_p1() {
+        let input = "light red bags contain 1 bright white bag, 2 muted yellow bags.
+dark orange bags contain 3 bright white bags, 4 muted yellow bags.
+bright white bags contain 1 shiny gold bag.
+muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
+shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
+dark olive bags contain 3 faded blue bags, 4 dotted black bags.
+vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
+faded blue bags contain no other bags.
+dotted black bags contain no other bags.";
+
+        let bags = parse(&input.split("\n").map(String::from).collect::<Vec<String>>()).unwrap();
+        let res = part1(&bags);
+        assert_eq!(4, res);
+    }
+
+    #[test]
+    fn test_p2_1() {
+        let input = "light red bags contain 1 bright white bag, 2 muted yellow bags.
+dark orange bags contain 3 bright white bags, 4 muted yellow bags.
+bright white bags contain 1 shiny gold bag.
+muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
+shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
+dark olive bags contain 3 faded blue bags, 4 dotted black bags.
+vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
+faded blue bags contain no other bags.
+dotted black bags contain no other bags.";
+
+        let bags = parse(&input.split("\n").map(String::from).collect::<Vec<String>>()).unwrap();
+        let res = part2(&bags).unwrap();
+        assert_eq!(32, res);
+    }
+
+    #[test]
+    fn test_p2_2() {
+        let input = "shiny gold bags contain 2 dark red bags.
+dark red bags contain 2 dark orange bags.
+dark orange bags contain 2 dark yellow bags.
+dark yellow bags contain 2 dark green bags.
+dark green bags contain 2 dark blue bags.
+dark blue bags contain 2 dark violet bags.
+dark violet bags contain no other bags.";
+
+        let bags = parse(&input.split("\n").map(String::from).collect::<Vec<String>>()).unwrap();
+        let res = part2(&bags).unwrap();
+        assert_eq!(126, res);
+    }
+
+    #[test]
+    fn test_explain() {
+        let input = "shiny gold bags contain 2 dark red bags, 1 faded blue bag.
+dark red bags contain 3 faded blue bags.
+bright white bags contain 1 shiny gold bag.
+faded blue bags contain no other bags.";
+
+        let input = input.split('\n').map(String::from).collect::<Vec<String>>();
+        let steps = explain(&input)
+            .unwrap()
+            .iter()
+            .map(|s| s.to_string())
+            .collect::<Vec<String>>();
+
+        assert_eq!(
+            vec![
+                "[part 1] container color=bright white count=1",
+                "[part 2] contents color=faded blue bags=0",
+                "[part 2] contents color=dark red bags=3",
+                "[part 2] contents color=shiny gold bags=9",
+            ],
+            steps
+        );
+    }
+
+    #[test]
+    fn test_no_shiny_gold() {
+        let input = vec![String::from("faded blue bags contain no other bags.")];
+        let bags = parse(&input).unwrap();
+
+        assert_eq!((0, Ok(0)), (part1(&bags), part2(&bags)));
+    }
+
+    #[test]
+    fn test_cycle() {
+        let input = "shiny gold bags contain 1 dark red bag.
+dark red bags contain 2 bright white bags.
+bright white bags contain 1 dark red bag.";
+
+        let input = input.split('\n').map(String::from).collect::<Vec<String>>();
+        let err = solve(&input).err().unwrap();
+
+        assert_eq!((2, 0..8), (err.line, err.columns));
+        assert_eq!(
+            "bags can contain themselves: dark red -> bright white -> dark red",
+            err.message
+        );
+        assert!(explain(&input).is_err());
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = vec![
+            String::from("faded blue bags contain no other bags."),
+            String::from("shiny gold bags contain 2 dark red bags, two faded blue bags."),
+        ];
+
+        let err = parse(&input).unwrap_err();
+        assert_eq!(2, err.line);
+        assert_eq!(41..42, err.columns);
+        assert_eq!("expected a number", err.message);
+    }
+}
@@ -0,0 +1,250 @@
+//! Day 8: Handheld Halting
+
+use std::fmt::{self, Display};
+
+use aoc_2020::cycle::{self, Outcome};
+use aoc_2020::explain::{NoTrace, Step, Trace};
+use aoc_2020::parse::{int, literal, one_of, parse_lines, Parser};
+use aoc_2020::Diagnostic;
+
+/// A boot code instruction.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Instr {
+    Nop(i32),
+    Acc(i32),
+    Jmp(i32),
+}
+
+impl Instr {
+    /// The instruction with `nop` and `jmp` swapped.
+    pub fn switched(self) -> Self {
+        match self {
+            Instr::Nop(n) => Instr::Jmp(n),
+            Instr::Jmp(n) => Instr::Nop(n),
+            Instr::Acc(n) => Instr::Acc(n),
+        }
+    }
+}
+
+impl Display for Instr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Instr::Nop(n) => write!(f, "nop {:+}", n),
+            Instr::Acc(n) => write!(f, "acc {:+}", n),
+            Instr::Jmp(n) => write!(f, "jmp {:+}", n),
+        }
+    }
+}
+
+/// Parses the boot code, one instruction per line.
+pub fn parse(input: &[String]) -> Result<Vec<Instr>, Diagnostic> {
+    let instr =
+        (one_of(&["nop", "acc", "jmp"]), literal(" "), int::<i32>()).map(|(op, _, n)| match op {
+            "nop" => Instr::Nop(n),
+            "acc" => Instr::Acc(n),
+            _ => Instr::Jmp(n),
+        });
+
+    parse_lines(&instr, input)
+}
+
+/// Runs the program, with the instruction at `idx_to_switch` switched, until it leaves the
+/// instructions or is about to execute one a second time. States are `(pc, acc)`.
+fn run(
+    instructions: &[Instr],
+    idx_to_switch: Option<usize>,
+    trace: &mut impl Trace,
+) -> Outcome<(i32, i32)> {
+    cycle::run_until_repeat(
+        (0, 0),
+        |&(pc, _)| pc,
+        |&(pc, acc)| {
+            let mut instr = *instructions.get(pc as usize)?;
+            if Some(pc as usize) == idx_to_switch {
+                instr = instr.switched();
+            }
+
+            let next = match instr {
+                Instr::Nop(_) => (pc + 1, acc),
+                Instr::Acc(n) => (pc + 1, acc + n),
+                Instr::Jmp(n) => (pc + n, acc),
+            };
+            trace.step(|| {
+                Step::new(1, "exec")
+                    .with("pc", pc)
+                    .with("instr", instr)
+                    .with("acc", next.1)
+            });
+
+            Some(next)
+        },
+    )
+}
+
+/// Value of the accumulator right before an instruction is executed a second time.
+pub fn part1(instructions: &[Instr]) -> i32 {
+    part1_traced(instructions, &mut NoTrace)
+}
+
+/// Same as `part1`, tracing each instruction executed and the accumulator after it.
+pub fn part1_traced(instructions: &[Instr], trace: &mut impl Trace) -> i32 {
+    match run(instructions, None, trace) {
+        Outcome::Repeated {
+            state: (_, acc), ..
+        } => acc,
+        Outcome::Halted { .. } => panic!("Program halted"),
+    }
+}
+
+/// Runs the program with the `nop`/`jmp` at `idx_to_switch` swapped.
+///
+/// Returns whether the program terminated by running past its last instruction, along with
+/// the value of the accumulator when it stopped.
+pub fn exec_with_switch(instructions: &[Instr], idx_to_switch: usize) -> (bool, i32) {
+    match run(instructions, Some(idx_to_switch), &mut NoTrace) {
+        Outcome::Halted {
+            state: (pc, acc), ..
+        } => (pc as usize == instructions.len(), acc),
+        Outcome::Repeated {
+            state: (_, acc), ..
+        } => (false, acc),
+    }
+}
+
+/// Value of the accumulator after the program terminates, once the corrupted instruction
+/// is fixed.
+pub fn part2(instructions: &[Instr]) -> i32 {
+    part2_traced(instructions, &mut NoTrace)
+}
+
+/// Same as `part2`, tracing each instruction switched and how the program ended with it.
+pub fn part2_traced(instructions: &[Instr], trace: &mut impl Trace) -> i32 {
+    for (idx, instr) in instructions.iter().enumerate() {
+        if let Instr::Acc(_) = instr {
+            continue;
+        }
+
+        let (did_halt, acc) = exec_with_switch(instructions, idx);
+        trace.step(|| {
+            Step::new(2, "switch")
+                .with("pc", idx)
+                .with("instr", instr.switched())
+                .with("halted", did_halt)
+                .with("acc", acc)
+        });
+
+        if did_halt {
+            return acc;
+        }
+    }
+
+    panic!("Program never halted");
+}
+
+/// Solves both parts of the puzzle.
+pub fn solve(input: &[String]) -> Result<(impl Display, impl Display), Diagnostic> {
+    let instructions = parse(input)?;
+
+    let p1 = part1(&instructions);
+    let p2 = part2(&instructions);
+
+    Ok((p1, p2))
+}
+
+/// The steps of the solution.
+pub fn explain(input: &[String]) -> Result<Vec<Step>, Diagnostic> {
+    let instructions = parse(input)?;
+    let mut steps = Vec::new();
+    part1_traced(&instructions, &mut steps);
+    part2_traced(&instructions, &mut steps);
+
+    Ok(steps)
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::{explain, parse, part1, part2, Instr};
+
+    #[test]
+    fn test_p1() {
+        let input = "nop +0
+acc +1
+jmp +4
+acc +3
+jmp -3
+acc -99
+acc +1
+jmp -4
+acc +6";
+
+        let instr = parse(&input.split("\n").map(String::from).collect::<Vec<String>>()).unwrap();
+        let res = part1(&instr);
+        assert_eq!(5, res);
+    }
+
+    #[test]
+    fn test_p2() {
+        let input = "nop +0
+acc +1
+jmp +4
+acc +3
+jmp -3
+acc -99
+acc +1
+jmp -4
+acc +6";
+
+        let instr = parse(&input.split("\n").map(String::from).collect::<Vec<String>>()).unwrap();
+        let res = part2(&instr);
+        assert_eq!(8, res);
+    }
+
+    #[test]
+    fn test_explain() {
+        let input = "nop +0
+acc +1
+jmp +4
+acc +3
+jmp -3
+acc -99
+acc +1
+jmp -4
+acc +6";
+
+        let input = input.split('\n').map(String::from).collect::<Vec<String>>();
+        let steps = explain(&input).unwrap();
+        let (p1, p2) = steps.split_at(steps.iter().position(|s| s.part == 2).unwrap());
+
+        assert_eq!(7, p1.len());
+        assert_eq!("[part 1] exec pc=0 instr=nop +0 acc=0", p1[0].to_string());
+        assert_eq!("[part 1] exec pc=4 instr=jmp -3 acc=5", p1[6].to_string());
+        assert_eq!(
+            vec![
+                "[part 2] switch pc=0 instr=jmp +0 halted=false acc=0",
+                "[part 2] switch pc=2 instr=nop +4 halted=false acc=4",
+                "[part 2] switch pc=4 instr=nop -3 halted=false acc=-94",
+                "[part 2] switch pc=7 instr=nop -4 halted=true acc=8",
+            ],
+            p2.iter().map(|s| s.to_string()).collect::<Vec<String>>()
+        );
+    }
+
+    fn instr() -> impl Strategy<Value = Instr> {
+        prop_oneof![
+            any::<i32>().prop_map(Instr::Nop),
+            any::<i32>().prop_map(Instr::Acc),
+            any::<i32>().prop_map(Instr::Jmp),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn prop_instr_round_trip(instructions in prop::collection::vec(instr(), 0..50)) {
+            let input = instructions.iter().map(|i| i.to_string()).collect::<Vec<String>>();
+
+            prop_assert_eq!(instructions, parse(&input).unwrap());
+        }
+    }
+}
@@ -0,0 +1,141 @@
+//! Random inputs for day 9.
+//!
+//! Every number after the preamble must be the sum of two of the 25 before it, so the smallest
+//! number of the window at least doubles every 25 numbers and a valid stream can't stay within
+//! an `i64` for more than about 1300 numbers. The generator grows it as slowly as it can, from a
+//! preamble of small numbers and by summing two of the three smallest of the window, and emits
+//! the longest valid prefix that fits (up to `size`), then the planted invalid number, then
+//! random filler up to `size` lines.
+
+use std::collections::VecDeque;
+
+use aoc_2020::gen::{rng, GenRng, Generated, Rng, SliceRandom};
+
+const PREAMBLE: usize = 25;
+/// Bound of the valid numbers, low enough for the sum of the last 10 to fit in an `i64`.
+const MAX_VALUE: i64 = 1 << 58;
+
+fn next_valid(rng: &mut GenRng, window: &VecDeque<i64>) -> i64 {
+    let mut smallest = window.iter().copied().collect::<Vec<i64>>();
+    smallest.sort_unstable();
+    smallest.truncate(3);
+
+    let pair = smallest.choose_multiple(rng, 2).collect::<Vec<&i64>>();
+    pair[0] + pair[1]
+}
+
+fn is_sum_of_two(window: &VecDeque<i64>, n: i64) -> bool {
+    window.iter().any(|a| window.contains(&(n - a)))
+}
+
+/// Number of contiguous ranges of at least two numbers summing to `target`.
+fn count_ranges(numbers: &[i64], target: i64) -> usize {
+    let mut count = 0;
+    let mut start = 0;
+    let mut sum = 0;
+
+    for (end, n) in numbers.iter().enumerate() {
+        sum += n;
+        while sum > target {
+            sum -= numbers[start];
+            start += 1;
+        }
+        if sum == target && end > start {
+            count += 1;
+        }
+    }
+
+    count
+}
+
+/// Generates a stream of `size` numbers whose invalid number and weakness are planted.
+pub fn generate(size: usize, seed: u64) -> Generated {
+    let mut rng = rng(seed);
+    let size = size.max(PREAMBLE + 3);
+
+    let mut numbers = Vec::with_capacity(size);
+    let mut window = VecDeque::with_capacity(PREAMBLE);
+    while numbers.len() < PREAMBLE {
+        let n = rng.gen_range(1..=50);
+        if !window.contains(&n) {
+            numbers.push(n);
+            window.push_back(n);
+        }
+    }
+
+    let push = |numbers: &mut Vec<i64>, window: &mut VecDeque<i64>, rng: &mut GenRng| {
+        let n = next_valid(rng, window);
+        numbers.push(n);
+        window.pop_front();
+        window.push_back(n);
+    };
+
+    while numbers.len() < size - 1 && next_valid(&mut rng.clone(), &window) < MAX_VALUE {
+        push(&mut numbers, &mut window, &mut rng);
+    }
+
+    // The invalid number is the sum of the last few numbers, which makes it larger than all of
+    // them so the weakness search stops there.
+    let (target, range) = loop {
+        let mut lengths = (2..=10).collect::<Vec<usize>>();
+        lengths.shuffle(&mut rng);
+
+        let found = lengths.into_iter().find_map(|k| {
+            let range = &numbers[numbers.len() - k..];
+            let target = range.iter().sum();
+            if !is_sum_of_two(&window, target) && count_ranges(&numbers, target) == 1 {
+                Some((target, range.to_vec()))
+            } else {
+                None
+            }
+        });
+
+        match found {
+            Some(found) => break found,
+            None => push(&mut numbers, &mut window, &mut rng),
+        }
+    };
+
+    let weakness = range.iter().min().unwrap() + range.iter().max().unwrap();
+
+    numbers.push(target);
+    while numbers.len() < size {
+        numbers.push(rng.gen_range(1..MAX_VALUE));
+    }
+
+    Generated::with_answers(
+        numbers.iter().map(|n| n.to_string()).collect(),
+        target,
+        weakness,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::gen::generate;
+    use crate::solve;
+
+    #[test]
+    fn test_generate() {
+        for seed in 0..10 {
+            let gen = generate(2000, seed);
+            let (p1, p2) = solve(&gen.input).unwrap();
+
+            assert_eq!(2000, gen.input.len());
+            assert_eq!(gen.part1, Some(p1.to_string()));
+            assert_eq!(gen.part2, Some(p2.to_string()));
+
+            let at = gen.input.iter().position(|n| Some(n) == gen.part1.as_ref());
+            assert!(
+                at.unwrap() > 1200,
+                "seed {}: invalid number at {:?}",
+                seed,
+                at
+            );
+        }
+
+        // Below the bound, the invalid number is the last line.
+        let gen = generate(1000, 0);
+        assert_eq!(gen.part1.as_ref(), gen.input.last());
+    }
+}
@@ -0,0 +1,192 @@
+//! Day 9: Encoding Error
+
+use std::collections::VecDeque;
+use std::fmt::Display;
+
+use aoc_2020::explain::{NoTrace, Step, Trace};
+use aoc_2020::{parse_ints, Diagnostic};
+
+pub mod gen;
+
+/// First number that isn't the sum of two of the `sample_size` numbers before it.
+pub fn part1(input: &[i64], sample_size: usize) -> i64 {
+    part1_traced(input, sample_size, &mut NoTrace)
+}
+
+/// Same as `part1`, tracing the window checked for each number and the pair found in it.
+pub fn part1_traced(input: &[i64], sample_size: usize, trace: &mut impl Trace) -> i64 {
+    let mut sample: VecDeque<i64> = input.iter().take(sample_size).copied().collect();
+
+    for (idx, tgt) in input.iter().enumerate().skip(sample_size) {
+        let pair = sample.iter().find(|&&n| sample.contains(&(tgt - n)));
+
+        trace.step(|| {
+            let step = Step::new(1, "window")
+                .with("start", idx - sample_size)
+                .with("end", idx - 1)
+                .with("number", tgt);
+            match pair {
+                Some(n) => step.with("pair", format!("{}+{}", n, tgt - n)),
+                None => step.with("pair", "none"),
+            }
+        });
+
+        if pair.is_some() {
+            sample.pop_front();
+            sample.push_back(*tgt);
+        } else {
+            return *tgt;
+        }
+    }
+
+    panic!("Invalid number not found");
+}
+
+/// Smallest and largest numbers of the contiguous range summing to `target`.
+pub fn part2(input: &[i64], target: i64) -> (i64, i64) {
+    part2_traced(input, target, &mut NoTrace)
+}
+
+/// Same as `part2`, tracing the range considered after each number is added.
+pub fn part2_traced(input: &[i64], target: i64, trace: &mut impl Trace) -> (i64, i64) {
+    let mut s = 0;
+    let mut nums = VecDeque::new();
+
+    for (idx, n) in input.iter().copied().enumerate() {
+        if n >= target {
+            break;
+        }
+
+        s += n;
+        nums.push_back(n);
+
+        while s > target {
+            let k = nums.pop_front().unwrap();
+            s -= k;
+        }
+
+        trace.step(|| {
+            Step::new(2, "range")
+                .with("start", idx + 1 - nums.len())
+                .with("end", idx)
+                .with("sum", s)
+        });
+
+        if s == target {
+            return (*nums.iter().min().unwrap(), *nums.iter().max().unwrap());
+        }
+    }
+
+    panic!("Sequence not found");
+}
+
+/// Solves both parts of the puzzle.
+pub fn solve(input: &[String]) -> Result<(impl Display, impl Display), Diagnostic> {
+    let input = parse_ints(input)?;
+    let p1 = part1(&input, 25);
+    let p2 = part2(&input, p1);
+    Ok((p1, p2.0 + p2.1))
+}
+
+/// The steps of the solution.
+pub fn explain(input: &[String]) -> Result<Vec<Step>, Diagnostic> {
+    let input = parse_ints(input)?;
+    let mut steps = Vec::new();
+    let p1 = part1_traced(&input, 25, &mut steps);
+    part2_traced(&input, p1, &mut steps);
+
+    Ok(steps)
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_2020::explain::Step;
+
+    use crate::{part1, part1_traced, part2, part2_traced};
+
+    #[test]
+    fn test_p1() {
+        let input = "35
+20
+15
+25
+47
+40
+62
+55
+65
+95
+102
+117
+150
+182
+127
+219
+299
+277
+309
+576";
+
+        let input = input
+            .split('\n')
+            .map(|s| s.parse().unwrap())
+            .collect::<Vec<i64>>();
+        let res = part1(&input, 5);
+        assert_eq!(127, res);
+    }
+
+    #[test]
+    fn test_p2() {
+        let input = "35
+20
+15
+25
+47
+40
+62
+55
+65
+95
+102
+117
+150
+182
+127
+219
+299
+277
+309
+576";
+
+        let input = input
+            .split('\n')
+            .map(|s| s.parse().unwrap())
+            .collect::<Vec<i64>>();
+        let res = part2(&input, 127);
+        assert_eq!((15, 47), res);
+    }
+
+    #[test]
+    fn test_traced() {
+        let input = [
+            35, 20, 15, 25, 47, 40, 62, 55, 65, 95, 102, 117, 150, 182, 127,
+        ];
+
+        let mut steps: Vec<Step> = Vec::new();
+        let target = part1_traced(&input, 5, &mut steps);
+        part2_traced(&input, target, &mut steps);
+
+        assert_eq!(
+            "[part 1] window start=0 end=4 number=40 pair=20+20",
+            steps[0].to_string()
+        );
+        assert_eq!(
+            "[part 1] window start=9 end=13 number=127 pair=none",
+            steps[9].to_string()
+        );
+        assert_eq!(
+            "[part 2] range start=2 end=5 sum=127",
+            steps.last().unwrap().to_string()
+        );
+    }
+}
@@ -0,0 +1,21 @@
+//! Random inputs for day 10.
+//!
+//! Like the real inputs, consecutive adapters differ by 1 or 3 jolts, which `part2` relies on.
+
+use aoc_2020::gen::{rng, Generated, Rng, SliceRandom};
+
+/// Generates a bag of `size` adapters.
+pub fn generate(size: usize, seed: u64) -> Generated {
+    let mut rng = rng(seed);
+
+    let mut jolts = 0u64;
+    let mut adapters = (0..size.max(1))
+        .map(|_| {
+            jolts += if rng.gen_bool(0.7) { 1 } else { 3 };
+            jolts
+        })
+        .collect::<Vec<u64>>();
+    adapters.shuffle(&mut rng);
+
+    Generated::new(adapters.iter().map(|a| a.to_string()).collect())
+}
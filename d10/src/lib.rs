@@ -0,0 +1,293 @@
+//! Day 10: Adapter Array
+
+use std::fmt::Display;
+
+use itertools::Itertools;
+
+use aoc_2020::explain::{NoTrace, Step, Trace};
+use aoc_2020::memo::Memo;
+use aoc_2020::{parse_ints, Diagnostic};
+
+pub mod gen;
+pub mod reference;
+
+/// Number of 1-jolt differences multiplied by the number of 3-jolt differences.
+pub fn part1(input: &[u64]) -> u64 {
+    let values = input.iter().copied().sorted().collect::<Vec<u64>>();
+
+    let mut d1 = 0;
+    let mut d3 = 1;
+
+    let mut last = 0;
+
+    for i in values {
+        match i - last {
+            1 => d1 += 1,
+            3 => d3 += 1,
+            _ => {}
+        }
+
+        last = i;
+    }
+
+    d1 * d3
+}
+
+/// Number of ways to go through a run of `n` consecutive 1-jolt differences.
+pub fn tribonacci(n: u64) -> u64 {
+    tribonacci_memo(&mut Memo::new(), n)
+}
+
+/// Same as `tribonacci`, reusing and filling the values of `memo`.
+pub fn tribonacci_memo(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+    if n == 0 {
+        return 1;
+    }
+    if n <= 2 {
+        return n;
+    }
+
+    memo.get_or_insert_with(n, |memo| {
+        tribonacci_memo(memo, n - 1) + tribonacci_memo(memo, n - 2) + tribonacci_memo(memo, n - 3)
+    })
+}
+
+/// Number of distinct adapter arrangements connecting the outlet to the device.
+///
+/// Only correct when adapters differ by 1 or 3 jolts, as they do in the puzzle inputs.
+pub fn part2(input: &[u64]) -> u64 {
+    part2_traced(input, &mut NoTrace)
+}
+
+/// Same as `part2`, tracing each run of equal differences and its tribonacci factor.
+pub fn part2_traced(input: &[u64], trace: &mut impl Trace) -> u64 {
+    let values = std::iter::once(&0)
+        .chain(input.iter().sorted())
+        .tuple_windows::<(&u64, &u64)>()
+        .map(|(i, j)| j - i)
+        .collect::<Vec<u64>>();
+
+    // See https://stackoverflow.com/a/32717990
+    let values = values
+        .iter()
+        .copied()
+        .map(|c| (c, 1))
+        .coalesce(|(c, n), (d, m)| {
+            if c == d {
+                Ok((c, n + m))
+            } else {
+                Err(((c, n), (d, m)))
+            }
+        })
+        .collect::<Vec<(u64, u64)>>();
+
+    let mut memo = Memo::new();
+    let mut product = 1;
+    for &(difference, length) in &values {
+        let factor = if difference == 1 {
+            tribonacci_memo(&mut memo, length)
+        } else {
+            1
+        };
+        product *= factor;
+
+        trace.step(|| {
+            Step::new(2, "group")
+                .with("difference", difference)
+                .with("length", length)
+                .with("factor", factor)
+                .with("product", product)
+        });
+    }
+
+    product
+}
+
+/// Solves both parts of the puzzle.
+pub fn solve(input: &[String]) -> Result<(impl Display, impl Display), Diagnostic> {
+    let input = parse_ints(input)?;
+    let p1 = part1(&input);
+    let p2 = part2(&input);
+
+    Ok((p1, p2))
+}
+
+/// The steps of the solution.
+pub fn explain(input: &[String]) -> Result<Vec<Step>, Diagnostic> {
+    let input = parse_ints(input)?;
+    let mut steps = Vec::new();
+    part2_traced(&input, &mut steps);
+
+    Ok(steps)
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_2020::memo::Memo;
+
+    use crate::{explain, part1, part2, tribonacci, tribonacci_memo};
+
+    #[test]
+    fn test_tribonacci() {
+        let mut memo = Memo::new();
+        let values = (0..10)
+            .map(|n| tribonacci_memo(&mut memo, n))
+            .collect::<Vec<u64>>();
+
+        assert_eq!(vec![1, 1, 2, 4, 7, 13, 24, 44, 81, 149], values);
+        assert_eq!(values[9], tribonacci(9));
+        assert_eq!(7, memo.len());
+    }
+
+    #[test]
+    fn test_p1() {
+        let input = "28
+33
+18
+42
+31
+14
+46
+20
+48
+47
+24
+23
+49
+45
+19
+38
+39
+11
+1
+32
+25
+35
+8
+17
+7
+9
+4
+2
+34
+10
+3";
+
+        let input = input
+            .split('\n')
+            .map(|s| s.parse().unwrap())
+            .collect::<Vec<u64>>();
+        let res = part1(&input);
+        assert_eq!(220, res);
+    }
+
+    #[test]
+    fn test_p2_1() {
+        let input = "16
+10
+15
+5
+1
+11
+7
+19
+6
+12
+4";
+
+        let input = input
+            .split('\n')
+            .map(|s| s.parse().unwrap())
+            .collect::<Vec<u64>>();
+        let res = part2(&input);
+        assert_eq!(8, res);
+    }
+
+    #[test]
+    fn test_p2_runs_of_three() {
+        // 0-1-2-3 can skip 1, 2 or both: a run of three 1-jolt differences has 4 arrangements,
+        // which takes tribonacci(0) = 1.
+        assert_eq!(4, tribonacci(3));
+        assert_eq!(4, part2(&[1, 2, 3]));
+        assert_eq!(7, part2(&[1, 2, 3, 4]));
+        assert_eq!(16, part2(&[1, 2, 3, 6, 7, 8, 9]));
+    }
+
+    #[test]
+    fn test_p2_2() {
+        let input = "28
+33
+18
+42
+31
+14
+46
+20
+48
+47
+24
+23
+49
+45
+19
+38
+39
+11
+1
+32
+25
+35
+8
+17
+7
+9
+4
+2
+34
+10
+3";
+
+        let input = input
+            .split('\n')
+            .map(|s| s.parse().unwrap())
+            .collect::<Vec<u64>>();
+        let res = part2(&input);
+        assert_eq!(19208, res);
+    }
+
+    #[test]
+    fn test_explain() {
+        let input = "16
+10
+15
+5
+1
+11
+7
+19
+6
+12
+4";
+
+        let input = input.split('\n').map(String::from).collect::<Vec<String>>();
+        let steps = explain(&input)
+            .unwrap()
+            .iter()
+            .map(|s| s.to_string())
+            .collect::<Vec<String>>();
+
+        assert_eq!(
+            vec![
+                "[part 2] group difference=1 length=1 factor=1 product=1",
+                "[part 2] group difference=3 length=1 factor=1 product=1",
+                "[part 2] group difference=1 length=3 factor=4 product=4",
+                "[part 2] group difference=3 length=1 factor=1 product=4",
+                "[part 2] group difference=1 length=2 factor=2 product=8",
+                "[part 2] group difference=3 length=1 factor=1 product=8",
+                "[part 2] group difference=1 length=1 factor=1 product=8",
+                "[part 2] group difference=3 length=1 factor=1 product=8",
+            ],
+            steps
+        );
+    }
+}
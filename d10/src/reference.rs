@@ -0,0 +1,62 @@
+//! Slow but straightforward solutions, to check the fast ones against.
+
+use std::fmt::Display;
+
+use aoc_2020::{parse_ints, Diagnostic};
+
+/// Counts the differences between consecutive adapters, outlet and device included.
+pub fn part1(input: &[u64]) -> u64 {
+    let mut jolts = input.to_vec();
+    jolts.push(0);
+    jolts.sort_unstable();
+    jolts.push(jolts[jolts.len() - 1] + 3);
+
+    let diffs = jolts.windows(2).map(|w| w[1] - w[0]).collect::<Vec<u64>>();
+    let count = |d| diffs.iter().filter(|&&x| x == d).count() as u64;
+
+    count(1) * count(3)
+}
+
+/// Counts the arrangements ending at each adapter from the ones up to 3 jolts below it.
+pub fn part2(input: &[u64]) -> u64 {
+    let mut jolts = input.to_vec();
+    jolts.push(0);
+    jolts.sort_unstable();
+
+    let mut ways = vec![0u64; jolts.len()];
+    ways[0] = 1;
+
+    for i in 1..jolts.len() {
+        for j in 0..i {
+            if jolts[i] - jolts[j] <= 3 {
+                ways[i] += ways[j];
+            }
+        }
+    }
+
+    ways[jolts.len() - 1]
+}
+
+pub fn solve(input: &[String]) -> Result<(impl Display, impl Display), Diagnostic> {
+    let input = parse_ints(input)?;
+
+    Ok((part1(&input), part2(&input)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::gen::generate;
+    use crate::{reference, solve};
+
+    #[test]
+    fn test_against_reference() {
+        for seed in 0..100 {
+            let gen = generate(60, seed);
+            let (p1, p2) = solve(&gen.input).unwrap();
+            let (r1, r2) = reference::solve(&gen.input).unwrap();
+
+            assert_eq!(r1.to_string(), p1.to_string(), "seed {}", seed);
+            assert_eq!(r2.to_string(), p2.to_string(), "seed {}", seed);
+        }
+    }
+}
@@ -0,0 +1,84 @@
+//! Flat implementation: the seats each seat looks at are found once, before running the
+//! rules, and the rounds only go through the cells.
+
+use aoc_2020::grid::{Grid, NEIGHBOURS_8};
+
+use crate::Seat;
+
+/// Indexes of the seats looked at by each position, going at most `reach` positions in every
+/// direction.
+fn neighbours(plan: &Grid<Seat>, reach: usize) -> Vec<Vec<usize>> {
+    plan.positions()
+        .map(|pos| {
+            NEIGHBOURS_8
+                .iter()
+                .filter_map(|&dir| {
+                    plan.ray(pos, dir)
+                        .take(reach)
+                        .find(|&p| plan[p] != Seat::Floor)
+                        .map(|p| plan.index_of(p))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Same as `crate::count_occupied_seats`, looking at most `reach` positions away.
+pub fn count_occupied_seats(plan: &Grid<Seat>, threshold: usize, reach: usize) -> usize {
+    let neighbours = neighbours(plan, reach);
+    let mut seats = plan.cells().to_vec();
+    let mut next = seats.clone();
+
+    loop {
+        let mut changed = false;
+
+        for (idx, s) in seats.iter().enumerate() {
+            let occupied = || {
+                neighbours[idx]
+                    .iter()
+                    .filter(|&&n| seats[n] == Seat::Taken)
+                    .count()
+            };
+
+            next[idx] = match s {
+                Seat::Empty if occupied() == 0 => Seat::Taken,
+                Seat::Taken if occupied() >= threshold => Seat::Empty,
+                _ => *s,
+            };
+            changed |= next[idx] != *s;
+        }
+
+        if !changed {
+            break;
+        }
+
+        std::mem::swap(&mut seats, &mut next);
+    }
+
+    seats.iter().filter(|&&s| s == Seat::Taken).count()
+}
+
+pub fn part1(plan: &Grid<Seat>) -> usize {
+    count_occupied_seats(plan, 4, 1)
+}
+
+pub fn part2(plan: &Grid<Seat>) -> usize {
+    count_occupied_seats(plan, 5, usize::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::gen::generate;
+    use crate::{flat, parse, part1, part2};
+
+    #[test]
+    fn test_against_scan() {
+        for seed in 0..5 {
+            let input = generate(30, seed).input;
+            let plan = parse(&input).unwrap();
+
+            assert_eq!(part1(&mut plan.clone()), flat::part1(&plan));
+            assert_eq!(part2(&mut plan.clone()), flat::part2(&plan));
+        }
+    }
+}
@@ -0,0 +1,38 @@
+//! Random inputs for day 11.
+//!
+//! The final seating can't be predicted without running the simulation, so no answers are
+//! planted: these layouts are for stress testing.
+
+use aoc_2020::gen::{rng, Generated, Rng};
+
+/// Generates a `size` × `size` layout of empty seats, with about a fifth of floor.
+pub fn generate(size: usize, seed: u64) -> Generated {
+    let mut rng = rng(seed);
+    let size = size.max(1);
+
+    let input = (0..size)
+        .map(|_| {
+            (0..size)
+                .map(|_| if rng.gen_bool(0.2) { '.' } else { 'L' })
+                .collect()
+        })
+        .collect();
+
+    Generated::new(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::gen::generate;
+    use crate::solve;
+
+    #[test]
+    fn test_generate() {
+        let gen = generate(50, 0);
+
+        assert_eq!(50, gen.input.len());
+        assert!(gen.input.iter().all(|l| l.len() == 50));
+        assert_eq!(gen, generate(50, 0));
+        assert!(solve(&gen.input).is_ok());
+    }
+}
@@ -0,0 +1,314 @@
+//! Day 11: Seating System
+
+use std::collections::HashMap;
+use std::fmt::{self, Display};
+
+use aoc_2020::cycle;
+use aoc_2020::explain::{NoTrace, Step, Trace};
+use aoc_2020::grid::{Grid, Pos, NEIGHBOURS_8};
+use aoc_2020::{Diagnostic, Implementation};
+
+pub mod flat;
+pub mod gen;
+
+/// A position of the seat layout.
+#[derive(Debug, Copy, Clone, PartialOrd, PartialEq, Eq, Hash)]
+pub enum Seat {
+    Floor,
+    Empty,
+    Taken,
+}
+
+impl fmt::Display for Seat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let c = match self {
+            Seat::Floor => '.',
+            Seat::Empty => 'L',
+            Seat::Taken => '#',
+        };
+
+        write!(f, "{}", c)
+    }
+}
+
+/// Parses the seat layout, one row per line.
+pub fn parse(input: &[String]) -> Result<Grid<Seat>, Diagnostic> {
+    Grid::parse(input, |c| match c {
+        '.' => Some(Seat::Floor),
+        'L' => Some(Seat::Empty),
+        '#' => Some(Seat::Taken),
+        _ => None,
+    })
+}
+
+/// The seat directly next to `current_position` in `direction`, if any.
+pub fn get_seat_next_to_position(
+    plan: &Grid<Seat>,
+    current_position: Pos,
+    direction: &(isize, isize),
+) -> Option<Seat> {
+    let pos = plan.offset(current_position, *direction)?;
+
+    Some(plan[pos]).filter(|&s| s != Seat::Floor)
+}
+
+/// The first seat visible from `current_position` in `direction`, if any.
+pub fn get_first_seat_in_direction(
+    plan: &Grid<Seat>,
+    current_position: Pos,
+    direction: &(isize, isize),
+) -> Option<Seat> {
+    plan.ray(current_position, *direction)
+        .map(|pos| plan[pos])
+        .find(|&s| s != Seat::Floor)
+}
+
+/// Applies the seating rules once, returning whether any seat changed.
+///
+/// A seat is emptied when at least `threshold` of the seats found by `seat_finding_strategy`
+/// are occupied.
+pub fn apply_rules<F>(plan: &mut Grid<Seat>, threshold: u32, seat_finding_strategy: &F) -> bool
+where
+    F: Fn(&Grid<Seat>, Pos, &(isize, isize)) -> Option<Seat>,
+{
+    let mut changes = HashMap::new();
+
+    for pos in plan.positions() {
+        let s = &plan[pos];
+        if s == &Seat::Floor {
+            continue;
+        }
+
+        let occupied = NEIGHBOURS_8
+            .iter()
+            .map(|dir| match seat_finding_strategy(plan, pos, dir) {
+                Some(Seat::Taken) => 1,
+                _ => 0,
+            })
+            .sum::<u32>();
+
+        if s == &Seat::Empty && occupied == 0 {
+            changes.insert(pos, Seat::Taken);
+        } else if s == &Seat::Taken && occupied >= threshold {
+            changes.insert(pos, Seat::Empty);
+        }
+    }
+
+    let changed = !changes.is_empty();
+    for (pos, s) in changes {
+        plan[pos] = s;
+    }
+
+    changed
+}
+
+/// Applies the seating rules until nothing changes and returns the number of occupied seats.
+pub fn count_occupied_seats<F>(
+    plan: &mut Grid<Seat>,
+    threshold: u32,
+    seat_finding_strategy: F,
+) -> usize
+where
+    F: Fn(&Grid<Seat>, Pos, &(isize, isize)) -> Option<Seat>,
+{
+    count_occupied_seats_traced(plan, threshold, seat_finding_strategy, 1, &mut NoTrace)
+}
+
+/// Same as `count_occupied_seats`, tracing the occupied seats after each round as steps of
+/// `part`.
+pub fn count_occupied_seats_traced<F>(
+    plan: &mut Grid<Seat>,
+    threshold: u32,
+    seat_finding_strategy: F,
+    part: u8,
+    trace: &mut impl Trace,
+) -> usize
+where
+    F: Fn(&Grid<Seat>, Pos, &(isize, isize)) -> Option<Seat>,
+{
+    let occupied = |plan: &Grid<Seat>| plan.cells().iter().filter(|&s| s == &Seat::Taken).count();
+
+    let mut round = 0;
+    while apply_rules(plan, threshold, &seat_finding_strategy) {
+        round += 1;
+        trace.step(|| {
+            Step::new(part, "round")
+                .with("round", round)
+                .with("occupied", occupied(plan))
+        });
+    }
+
+    occupied(plan)
+}
+
+/// Number of occupied seats once the layout is stable, looking at adjacent seats.
+pub fn part1(plan: &mut Grid<Seat>) -> usize {
+    part1_traced(plan, &mut NoTrace)
+}
+
+/// Same as `part1`, tracing the occupied seats after each round.
+pub fn part1_traced(plan: &mut Grid<Seat>, trace: &mut impl Trace) -> usize {
+    count_occupied_seats_traced(plan, 4, get_seat_next_to_position, 1, trace)
+}
+
+/// Number of occupied seats once the layout is stable, looking at visible seats.
+pub fn part2(plan: &mut Grid<Seat>) -> usize {
+    part2_traced(plan, &mut NoTrace)
+}
+
+/// Same as `part2`, tracing the occupied seats after each round.
+pub fn part2_traced(plan: &mut Grid<Seat>, trace: &mut impl Trace) -> usize {
+    count_occupied_seats_traced(plan, 5, get_first_seat_in_direction, 2, trace)
+}
+
+pub const IMPLEMENTATIONS: &[Implementation] = &[
+    Implementation {
+        name: "scan",
+        part: 1,
+        solve: |input| Ok(part1(&mut parse(input)?).to_string()),
+    },
+    Implementation {
+        name: "flat",
+        part: 1,
+        solve: |input| Ok(flat::part1(&parse(input)?).to_string()),
+    },
+    Implementation {
+        name: "scan",
+        part: 2,
+        solve: |input| Ok(part2(&mut parse(input)?).to_string()),
+    },
+    Implementation {
+        name: "flat",
+        part: 2,
+        solve: |input| Ok(flat::part2(&parse(input)?).to_string()),
+    },
+];
+
+fn render(plan: &Grid<Seat>) -> Vec<String> {
+    plan.to_string().lines().map(String::from).collect()
+}
+
+/// The seat layout after each round of `part`, from the initial one to the stable one.
+pub fn frames(input: &[String], part: u8) -> Result<Vec<Vec<String>>, Diagnostic> {
+    let plan = parse(input)?;
+    if plan.height() == 0 {
+        return Ok(vec![]);
+    }
+
+    let history = cycle::history(plan, |plan| {
+        let mut next = plan.clone();
+        if part == 1 {
+            apply_rules(&mut next, 4, &get_seat_next_to_position);
+        } else {
+            apply_rules(&mut next, 5, &get_first_seat_in_direction);
+        }
+        next
+    });
+
+    Ok(history.states.iter().map(render).collect())
+}
+
+/// Solves both parts of the puzzle.
+pub fn solve(input: &[String]) -> Result<(impl Display, impl Display), Diagnostic> {
+    let mut plan = parse(input)?;
+    let p1 = part1(&mut plan);
+
+    let mut plan = parse(input)?;
+    let p2 = part2(&mut plan);
+
+    Ok((p1, p2))
+}
+
+/// The steps of the solution.
+pub fn explain(input: &[String]) -> Result<Vec<Step>, Diagnostic> {
+    let mut steps = Vec::new();
+    part1_traced(&mut parse(input)?, &mut steps);
+    part2_traced(&mut parse(input)?, &mut steps);
+
+    Ok(steps)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{explain, frames, parse, part1, part2};
+
+    #[test]
+    fn test_p1() {
+        let input = "L.LL.LL.LL
+LLLLLLL.LL
+L.L.L..L..
+LLLL.LL.LL
+L.LL.LL.LL
+L.LLLLL.LL
+..L.L.....
+LLLLLLLLLL
+L.LLLLLL.L
+L.LLLLL.LL";
+
+        let input = input.split('\n').map(String::from).collect::<Vec<String>>();
+        let res = part1(&mut parse(&input).unwrap());
+
+        assert_eq!(37, res);
+    }
+
+    #[test]
+    fn test_p2() {
+        let input = "L.LL.LL.LL
+LLLLLLL.LL
+L.L.L..L..
+LLLL.LL.LL
+L.LL.LL.LL
+L.LLLLL.LL
+..L.L.....
+LLLLLLLLLL
+L.LLLLLL.L
+L.LLLLL.LL";
+
+        let input = input.split('\n').map(String::from).collect::<Vec<String>>();
+        let res = part2(&mut parse(&input).unwrap());
+
+        assert_eq!(26, res);
+    }
+
+    #[test]
+    fn test_explain() {
+        let input = "L.LL.LL.LL
+LLLLLLL.LL
+L.L.L..L..
+LLLL.LL.LL
+L.LL.LL.LL
+L.LLLLL.LL
+..L.L.....
+LLLLLLLLLL
+L.LLLLLL.L
+L.LLLLL.LL";
+
+        let input = input.split('\n').map(String::from).collect::<Vec<String>>();
+        let steps = explain(&input).unwrap();
+        let (p1, p2): (Vec<_>, Vec<_>) = steps.iter().partition(|s| s.part == 1);
+
+        assert_eq!(5, p1.len());
+        assert_eq!("[part 1] round round=1 occupied=71", p1[0].to_string());
+        assert_eq!(Some("37"), p1[4].get("occupied"));
+        assert_eq!(6, p2.len());
+        assert_eq!(Some("26"), p2[5].get("occupied"));
+    }
+
+    #[test]
+    fn test_frames() {
+        let input = ["L.L", "LLL", "L.L"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect::<Vec<String>>();
+        let frames = frames(&input, 1).unwrap();
+
+        assert_eq!(
+            vec![
+                vec!["L.L", "LLL", "L.L"],
+                vec!["#.#", "###", "#.#"],
+                vec!["#.#", "#L#", "#.#"],
+            ],
+            frames
+        );
+    }
+}
@@ -0,0 +1,288 @@
+//! Day 12: Rain Risk
+
+use std::fmt::{self, Display};
+use std::ops::Range;
+use std::str::FromStr;
+
+use aoc_2020::explain::{NoTrace, Step, Trace};
+use aoc_2020::point::{Direction, Point2};
+use aoc_2020::Diagnostic;
+
+/// Error returned when an instruction can't be parsed, with the offending columns.
+pub struct ParseInstructionError {
+    pub columns: Range<usize>,
+    pub message: String,
+}
+
+/// A navigation instruction.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Instruction {
+    North(u32),
+    South(u32),
+    East(u32),
+    West(u32),
+    Left(u32),
+    Right(u32),
+    Forward(u32),
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Instruction::North(c) => write!(f, "N{}", c),
+            Instruction::South(c) => write!(f, "S{}", c),
+            Instruction::East(c) => write!(f, "E{}", c),
+            Instruction::West(c) => write!(f, "W{}", c),
+            Instruction::Left(c) => write!(f, "L{}", c),
+            Instruction::Right(c) => write!(f, "R{}", c),
+            Instruction::Forward(c) => write!(f, "F{}", c),
+        }
+    }
+}
+
+impl FromStr for Instruction {
+    type Err = ParseInstructionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        let instr = match chars.next() {
+            Some(c) => match c {
+                'N' => Instruction::North,
+                'S' => Instruction::South,
+                'E' => Instruction::East,
+                'W' => Instruction::West,
+                'L' => Instruction::Left,
+                'R' => Instruction::Right,
+                'F' => Instruction::Forward,
+                _ => {
+                    return Err(ParseInstructionError {
+                        columns: 0..c.len_utf8(),
+                        message: format!("invalid action '{}'", c),
+                    })
+                }
+            },
+            None => {
+                return Err(ParseInstructionError {
+                    columns: 0..0,
+                    message: String::from("empty instruction"),
+                })
+            }
+        };
+
+        match chars.as_str().parse::<u32>() {
+            Ok(c) => Ok(instr(c)),
+            Err(e) => Err(ParseInstructionError {
+                columns: s.len() - chars.as_str().len()..s.len(),
+                message: format!("invalid value: {}", e),
+            }),
+        }
+    }
+}
+
+/// The ship position and its waypoint, relative to the ship.
+pub struct Ship {
+    pub pos: Point2,
+    pub waypoint: Point2,
+}
+
+impl Ship {
+    /// Creates a ship at the origin with the given waypoint.
+    pub fn new(waypoint: Point2) -> Self {
+        Ship {
+            pos: Point2::ORIGIN,
+            waypoint,
+        }
+    }
+
+    /// Moves the ship by `vec`.
+    pub fn move_ship(&mut self, vec: Point2) {
+        self.pos += vec;
+    }
+
+    /// Moves the waypoint by `vec`.
+    pub fn move_waypoint(&mut self, vec: Point2) {
+        self.waypoint += vec;
+    }
+
+    /// Applies `instr`, using `move_fn` for the cardinal direction instructions.
+    ///
+    /// Rotations are rounded down to a multiple of 90 degrees.
+    pub fn apply_instruction(&mut self, instr: &Instruction, move_fn: &dyn Fn(&mut Self, Point2)) {
+        match *instr {
+            Instruction::North(c) => move_fn(self, Direction::North.vector() * c as i64),
+            Instruction::South(c) => move_fn(self, Direction::South.vector() * c as i64),
+            Instruction::East(c) => move_fn(self, Direction::East.vector() * c as i64),
+            Instruction::West(c) => move_fn(self, Direction::West.vector() * c as i64),
+            Instruction::Left(deg) => self.waypoint = self.waypoint.rotate((deg / 90) as i64),
+            Instruction::Right(deg) => self.waypoint = self.waypoint.rotate(-((deg / 90) as i64)),
+            Instruction::Forward(c) => self.move_ship(self.waypoint * c as i64),
+        };
+    }
+}
+
+/// Parses the navigation instructions, one per line.
+pub fn parse(input: &[String]) -> Result<Vec<Instruction>, Diagnostic> {
+    input
+        .iter()
+        .enumerate()
+        .map(|(idx, i)| {
+            Instruction::from_str(i.as_str())
+                .map_err(|e| Diagnostic::new(idx, i, e.columns, e.message))
+        })
+        .collect()
+}
+
+/// Applies the instructions to `ship`, tracing its position and waypoint after each one as
+/// steps of `part`, and returns the Manhattan distance travelled.
+fn navigate(
+    instr: &[Instruction],
+    mut ship: Ship,
+    move_fn: &dyn Fn(&mut Ship, Point2),
+    part: u8,
+    trace: &mut impl Trace,
+) -> u32 {
+    for i in instr {
+        ship.apply_instruction(i, move_fn);
+        trace.step(|| {
+            Step::new(part, "move")
+                .with("instr", i)
+                .with("pos", ship.pos)
+                .with("waypoint", ship.waypoint)
+        });
+    }
+
+    ship.pos.norm1() as u32
+}
+
+/// Manhattan distance travelled when the instructions move the ship.
+pub fn part1(instr: &[Instruction]) -> u32 {
+    part1_traced(instr, &mut NoTrace)
+}
+
+/// Same as `part1`, tracing the ship after each instruction.
+pub fn part1_traced(instr: &[Instruction], trace: &mut impl Trace) -> u32 {
+    let ship = Ship::new(Direction::East.vector());
+
+    navigate(instr, ship, &Ship::move_ship, 1, trace)
+}
+
+/// Manhattan distance travelled when the instructions move the waypoint.
+pub fn part2(instr: &[Instruction]) -> u32 {
+    part2_traced(instr, &mut NoTrace)
+}
+
+/// Same as `part2`, tracing the ship after each instruction.
+pub fn part2_traced(instr: &[Instruction], trace: &mut impl Trace) -> u32 {
+    let ship = Ship::new(Point2::new(10, 1));
+
+    navigate(instr, ship, &Ship::move_waypoint, 2, trace)
+}
+
+/// Solves both parts of the puzzle.
+pub fn solve(input: &[String]) -> Result<(impl Display, impl Display), Diagnostic> {
+    let instr = parse(input)?;
+    let p1 = part1(&instr);
+    let p2 = part2(&instr);
+
+    Ok((p1, p2))
+}
+
+/// The steps of the solution.
+pub fn explain(input: &[String]) -> Result<Vec<Step>, Diagnostic> {
+    let instr = parse(input)?;
+    let mut steps = Vec::new();
+    part1_traced(&instr, &mut steps);
+    part2_traced(&instr, &mut steps);
+
+    Ok(steps)
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::{explain, parse, part1, part2, Instruction};
+
+    #[test]
+    fn test_p1() {
+        let input = "F10
+N3
+F7
+R90
+F11";
+
+        let input = input.split('\n').map(String::from).collect::<Vec<String>>();
+        let res = part1(&parse(&input).unwrap());
+
+        assert_eq!(25, res);
+    }
+
+    #[test]
+    fn test_p2() {
+        let input = "F10
+N3
+F7
+R90
+F11";
+
+        let input = input.split('\n').map(String::from).collect::<Vec<String>>();
+        let res = part2(&parse(&input).unwrap());
+
+        assert_eq!(286, res);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = "F10
+N3
+X7
+F11";
+
+        let input = input.split('\n').map(String::from).collect::<Vec<String>>();
+        let err = parse(&input).err().unwrap();
+
+        assert_eq!(3, err.line);
+        assert_eq!(0..1, err.columns);
+        assert_eq!("invalid action 'X'", err.message);
+    }
+
+    #[test]
+    fn test_explain() {
+        let input = "F10
+N3
+F7
+R90
+F11";
+
+        let input = input.split('\n').map(String::from).collect::<Vec<String>>();
+        let steps = explain(&input).unwrap();
+
+        assert_eq!(10, steps.len());
+        assert_eq!(
+            "[part 1] move instr=F10 pos=(10, 0) waypoint=(1, 0)",
+            steps[0].to_string()
+        );
+        assert_eq!(Some("(214, -72)"), steps[9].get("pos"));
+    }
+
+    fn instruction() -> impl Strategy<Value = Instruction> {
+        prop_oneof![
+            any::<u32>().prop_map(Instruction::North),
+            any::<u32>().prop_map(Instruction::South),
+            any::<u32>().prop_map(Instruction::East),
+            any::<u32>().prop_map(Instruction::West),
+            any::<u32>().prop_map(Instruction::Left),
+            any::<u32>().prop_map(Instruction::Right),
+            any::<u32>().prop_map(Instruction::Forward),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn prop_instruction_round_trip(instructions in prop::collection::vec(instruction(), 0..50)) {
+            let input = instructions.iter().map(|i| i.to_string()).collect::<Vec<String>>();
+
+            prop_assert_eq!(instructions, parse(&input).unwrap());
+        }
+    }
+}
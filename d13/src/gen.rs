@@ -0,0 +1,88 @@
+//! Random inputs for day 13.
+//!
+//! The answer of part 2 is picked first, after a few leading `x`s, then each bus is placed at
+//! the first offset that makes it depart at the right time. Bus ids are distinct primes among the first `2 * size`, dropping
+//! the ones that would make the answer overflow.
+
+use aoc_2020::gen::{rng, Generated, Rng, SliceRandom};
+
+const MAX_PERIOD: u64 = 1 << 60;
+
+fn primes(n: usize) -> Vec<u64> {
+    let mut primes = Vec::with_capacity(n);
+    let mut candidate = 2;
+
+    while primes.len() < n {
+        if primes.iter().all(|p| candidate % p != 0) {
+            primes.push(candidate);
+        }
+        candidate += 1;
+    }
+
+    primes
+}
+
+/// Generates notes with up to `size` buses, planting the answer of part 2.
+pub fn generate(size: usize, seed: u64) -> Generated {
+    let mut rng = rng(seed);
+    let size = size.max(1);
+
+    let mut pool = primes(2 * size);
+    pool.shuffle(&mut rng);
+
+    let mut period = 1u64;
+    let numbers = pool
+        .into_iter()
+        .filter(|&p| match period.checked_mul(p) {
+            Some(n) if n <= MAX_PERIOD => {
+                period = n;
+                true
+            }
+            _ => false,
+        })
+        .take(size)
+        .collect::<Vec<u64>>();
+    let period = numbers.iter().product::<u64>();
+
+    // The first bus isn't always at offset 0.
+    let first = numbers[0];
+    let lead = rng.gen_range(0..=2);
+    let ts = first * rng.gen_range(1..=period / first) - lead;
+
+    let mut schedule = vec![String::from("x"); lead as usize];
+    schedule.push(first.to_string());
+    for &n in &numbers[1..] {
+        let phase = (n - ts % n) % n;
+        let next = schedule.len() as u64;
+        let offset = next + (phase + n - next % n) % n + n * rng.gen_range(0..=1);
+
+        schedule.resize(offset as usize, String::from("x"));
+        schedule.push(n.to_string());
+    }
+
+    Generated {
+        input: vec![
+            rng.gen_range(1..1_000_000u64).to_string(),
+            schedule.join(","),
+        ],
+        part1: None,
+        part2: Some(ts.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::gen::generate;
+    use crate::solve;
+
+    #[test]
+    fn test_generate() {
+        for seed in 0..20 {
+            let gen = generate(9, seed);
+            let (_, p2) = solve(&gen.input).unwrap();
+
+            assert_eq!(gen.part2, Some(p2.to_string()));
+        }
+        assert!((0..20).any(|seed| generate(9, seed).input[1].starts_with('x')));
+    }
+}
@@ -0,0 +1,293 @@
+//! Day 13: Shuttle Search
+
+use std::fmt::Display;
+
+use aoc_2020::explain::{NoTrace, Step, Trace};
+use aoc_2020::math::{crt, CrtError};
+use aoc_2020::parse::{literal, parse_line, separated, uint, Parser};
+use aoc_2020::Diagnostic;
+
+pub mod gen;
+pub mod reference;
+
+/// A bus in service, and its position in the schedule.
+#[derive(Debug)]
+pub struct Bus {
+    pub number: u64,
+    pub offset: u64,
+}
+
+/// Parses the earliest departure timestamp and the buses in service.
+pub fn parse(input: &[String]) -> Result<(u64, Vec<Bus>), Diagnostic> {
+    if input.len() < 2 {
+        let idx = input.len();
+        return Err(Diagnostic::new(
+            idx,
+            "",
+            0..0,
+            "expected the buses in service",
+        ));
+    }
+
+    let ts = parse_line(&uint::<u64>(), 0, &input[0])?;
+
+    let number = uint::<u64>().try_map(|n| {
+        if n > 0 {
+            Ok(Some(n))
+        } else {
+            Err("bus ids start at 1")
+        }
+    });
+    let schedule = separated(number.or(literal("x").map(|_| None)), literal(","));
+    let buses = parse_line(&schedule, 1, &input[1])?
+        .into_iter()
+        .enumerate()
+        .filter_map(|(idx, number)| {
+            number.map(|number| Bus {
+                number,
+                offset: idx as u64,
+            })
+        })
+        .collect::<Vec<Bus>>();
+    if buses.is_empty() {
+        return Err(Diagnostic::at_line(1, &input[1], "no bus in service"));
+    }
+
+    Ok((ts, buses))
+}
+
+/// Id of the earliest bus to take multiplied by the number of minutes to wait for it.
+pub fn part1(ts: u64, buses: &[Bus]) -> u64 {
+    let bus_wait_times = buses
+        .iter()
+        .map(|b| (b.number, (b.number - ts % b.number) % b.number))
+        .collect::<Vec<(u64, u64)>>();
+
+    let next_bus = bus_wait_times.iter().min_by_key(|b| b.1).unwrap();
+
+    next_bus.0 * next_bus.1
+}
+
+/// Combines two `(period, phase)` schedules into the schedule where both coincide, if they
+/// ever do.
+pub fn sync(period1: u64, phase1: u64, period2: u64, phase2: u64) -> Result<(u64, u64), CrtError> {
+    let (phase, period) = crt(vec![(phase1, period1), (phase2, period2)])?;
+
+    Ok((period, phase))
+}
+
+/// Phase at which a bus with id `number` must depart for it to leave `offset` minutes after
+/// the first bus.
+pub fn get_phase(number: u64, offset: u64) -> u64 {
+    (number - offset % number) % number
+}
+
+/// Earliest timestamp at which every bus departs at its offset, if there is one.
+pub fn part2(buses: &[Bus]) -> Result<u64, CrtError> {
+    part2_traced(buses, &mut NoTrace)
+}
+
+/// Same as `part2`, tracing the `(period, phase)` of the schedule as each bus is synced.
+pub fn part2_traced(buses: &[Bus], trace: &mut impl Trace) -> Result<u64, CrtError> {
+    // Every timestamp, which each bus narrows down to its own phase.
+    let (mut period, mut phase) = (1, 0);
+
+    for b in buses {
+        let bus_phase = get_phase(b.number, b.offset);
+        let r = sync(period, phase, b.number, bus_phase)?;
+        period = r.0;
+        phase = r.1;
+
+        trace.step(|| {
+            Step::new(2, "sync")
+                .with("bus", b.number)
+                .with("offset", b.offset)
+                .with("bus_phase", bus_phase)
+                .with("period", period)
+                .with("phase", phase)
+        });
+    }
+
+    Ok(phase)
+}
+
+/// Points at the schedule when the buses never depart at their offsets.
+fn unsyncable(input: &[String], e: CrtError) -> Diagnostic {
+    Diagnostic::at_line(1, &input[1], format!("impossible to sync the buses: {}", e))
+}
+
+/// Solves both parts of the puzzle.
+pub fn solve(input: &[String]) -> Result<(impl Display, impl Display), Diagnostic> {
+    let (ts, buses) = parse(input)?;
+    let p1 = part1(ts, &buses);
+    let p2 = part2(&buses).map_err(|e| unsyncable(input, e))?;
+
+    Ok((p1, p2))
+}
+
+/// The steps of the solution.
+pub fn explain(input: &[String]) -> Result<Vec<Step>, Diagnostic> {
+    let (_, buses) = parse(input)?;
+    let mut steps = Vec::new();
+    part2_traced(&buses, &mut steps).map_err(|e| unsyncable(input, e))?;
+
+    Ok(steps)
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use aoc_2020::math::CrtError;
+
+    use crate::{explain, parse, part1, part2, solve, sync, Bus};
+
+    #[test]
+    fn test_p1() {
+        let input = "939
+7,13,x,x,59,x,31,19";
+
+        let input = input.split('\n').map(String::from).collect::<Vec<String>>();
+        let (ts, buses) = parse(&input).unwrap();
+        let res = part1(ts, &buses);
+
+        assert_eq!(295, res);
+
+        let input = "1008832
+23,x,x,x,x,x,x,x,x,x,x,x,x,41,x,x,x,x,x,x,x,x,x,449,x,x,x,x,x,x,x,x,x,x,x,x,x,x,x,x,x,13,19,x,x,x,x,x,x,x,x,x,29,x,991,x,x,x,x,x,37,x,x,x,x,x,x,x,x,x,x,17";
+
+        let input = input.split('\n').map(String::from).collect::<Vec<String>>();
+        let (ts, buses) = parse(&input).unwrap();
+        let res = part1(ts, &buses);
+
+        assert_eq!(5946, res);
+    }
+
+    #[test]
+    fn test_p2() {
+        let input = "939
+7,13,x,x,59,x,31,19";
+
+        let input = input.split('\n').map(String::from).collect::<Vec<String>>();
+        let (_, buses) = parse(&input).unwrap();
+        let res = part2(&buses).unwrap();
+
+        assert_eq!(1068781, res);
+
+        let input = "1008832
+23,x,x,x,x,x,x,x,x,x,x,x,x,41,x,x,x,x,x,x,x,x,x,449,x,x,x,x,x,x,x,x,x,x,x,x,x,x,x,x,x,13,19,x,x,x,x,x,x,x,x,x,29,x,991,x,x,x,x,x,37,x,x,x,x,x,x,x,x,x,x,17";
+
+        let input = input.split('\n').map(String::from).collect::<Vec<String>>();
+        let (_, buses) = parse(&input).unwrap();
+        let res = part2(&buses).unwrap();
+
+        assert_eq!(645338524823718, res);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = vec![String::from("939"), String::from("7,13,y,0")];
+        let err = parse(&input).unwrap_err();
+        assert_eq!((2, 5..6), (err.line, err.columns));
+
+        let input = vec![String::from("939"), String::from("7,0")];
+        let err = parse(&input).unwrap_err();
+        assert_eq!((2, 2..3), (err.line, err.columns));
+        assert_eq!("bus ids start at 1", err.message);
+
+        let err = parse(&input[..1]).unwrap_err();
+        assert_eq!(2, err.line);
+        assert!(parse(&[String::from("x"), String::from("x")]).is_err());
+        assert!(parse(&[String::from("1"), String::from("x")]).is_err());
+    }
+
+    #[test]
+    fn test_p2_leading_x() {
+        let input = vec![String::from("939"), String::from("x,7,13")];
+        let (_, buses) = parse(&input).unwrap();
+
+        // 76 + 1 = 7 * 11 and 76 + 2 = 13 * 6.
+        assert_eq!(Ok(76), part2(&buses));
+    }
+
+    #[test]
+    fn test_sync() {
+        assert_eq!(Ok((91, 77)), sync(7, 0, 13, 12));
+        assert_eq!(Ok((45, 18)), sync(9, 0, 5, 3));
+        assert_eq!(Ok((570, 120)), sync(30, 0, 38, 6));
+        assert_eq!(Ok((570, 120)), sync(38, 6, 30, 0));
+        assert_eq!(Err(CrtError::Inconsistent), sync(4, 0, 6, 1));
+    }
+
+    #[test]
+    fn test_unsyncable() {
+        // Bus 4 departs on even timestamps, so bus 6 can't depart one minute later.
+        let input = vec![String::from("939"), String::from("4,6")];
+        let err = solve(&input).err().unwrap();
+
+        assert_eq!(2, err.line);
+        assert_eq!(
+            "impossible to sync the buses: inconsistent congruences",
+            err.message
+        );
+        assert!(explain(&input).is_err());
+    }
+
+    #[test]
+    fn test_explain() {
+        let input = "939
+7,13,x,x,59,x,31,19";
+
+        let input = input.split('\n').map(String::from).collect::<Vec<String>>();
+        let steps = explain(&input).unwrap();
+
+        assert_eq!(5, steps.len());
+        assert_eq!(
+            "[part 2] sync bus=7 offset=0 bus_phase=0 period=7 phase=0",
+            steps[0].to_string()
+        );
+        assert_eq!(
+            "[part 2] sync bus=13 offset=1 bus_phase=12 period=91 phase=77",
+            steps[1].to_string()
+        );
+        assert_eq!(Some("1068781"), steps[4].get("phase"));
+    }
+
+    const PRIMES: [u64; 15] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47];
+
+    /// Buses with distinct prime ids, after a few leading `x`s.
+    fn buses() -> impl Strategy<Value = Vec<Bus>> {
+        (
+            prop::sample::subsequence(PRIMES.to_vec(), 1..=6).prop_shuffle(),
+            0u64..20,
+            prop::collection::vec(1u64..20, 6),
+        )
+            .prop_map(|(numbers, lead, gaps)| {
+                let mut offset = lead;
+
+                numbers
+                    .iter()
+                    .zip(gaps)
+                    .enumerate()
+                    .map(|(idx, (&number, gap))| {
+                        if idx > 0 {
+                            offset += gap;
+                        }
+                        Bus { number, offset }
+                    })
+                    .collect()
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn prop_p2_satisfies_offsets(buses in buses()) {
+            let res = part2(&buses).unwrap();
+
+            for b in &buses {
+                prop_assert_eq!(0, (res + b.offset) % b.number, "bus {:?}", b);
+            }
+            prop_assert!(res < buses.iter().map(|b| b.number).product::<u64>());
+        }
+    }
+}
@@ -0,0 +1,101 @@
+//! Slow but straightforward solutions, to check the fast ones against.
+
+use std::fmt::Display;
+
+use aoc_2020::Diagnostic;
+
+use crate::{parse, Bus};
+
+/// Waits minute by minute until a bus departs.
+pub fn part1(ts: u64, buses: &[Bus]) -> u64 {
+    (ts..)
+        .find_map(|t| {
+            buses
+                .iter()
+                .find(|b| t % b.number == 0)
+                .map(|b| (t - ts) * b.number)
+        })
+        .unwrap()
+}
+
+/// Tries every departure of the first bus.
+pub fn part2(buses: &[Bus]) -> u64 {
+    let first = &buses[0];
+    let start = get_phase(first.number, first.offset);
+
+    (start..)
+        .step_by(first.number as usize)
+        .find(|t| buses.iter().all(|b| (t + b.offset) % b.number == 0))
+        .unwrap()
+}
+
+/// Tries every timestamp until both schedules coincide, then until they coincide again.
+pub fn sync(period1: u64, phase1: u64, period2: u64, phase2: u64) -> Option<(u64, u64)> {
+    let coincide = |n: &u64| n % period1 == phase1 && n % period2 == phase2;
+
+    let first = (0..period1 * period2).find(coincide)?;
+    let second = (first + 1..).find(coincide).unwrap();
+
+    Some((second - first, first))
+}
+
+/// First timestamp at which a bus must depart for it to leave `offset` minutes after `0`.
+pub fn get_phase(number: u64, offset: u64) -> u64 {
+    (0..).find(|n| (n + offset).is_multiple_of(number)).unwrap()
+}
+
+pub fn solve(input: &[String]) -> Result<(impl Display, impl Display), Diagnostic> {
+    let (ts, buses) = parse(input)?;
+
+    Ok((part1(ts, &buses), part2(&buses)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::gen::generate;
+    use crate::{get_phase, reference, solve, sync};
+
+    #[test]
+    fn test_against_reference() {
+        for seed in 0..100 {
+            let gen = generate(4, seed);
+            let (p1, p2) = solve(&gen.input).unwrap();
+            let (r1, r2) = reference::solve(&gen.input).unwrap();
+
+            assert_eq!(r1.to_string(), p1.to_string(), "seed {}", seed);
+            assert_eq!(r2.to_string(), p2.to_string(), "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn test_sync_against_reference() {
+        for period1 in 1..12 {
+            for period2 in 1..12 {
+                for phase1 in 0..period1 {
+                    for phase2 in 0..period2 {
+                        let res = sync(period1, phase1, period2, phase2);
+                        match reference::sync(period1, phase1, period2, phase2) {
+                            Some(expected) => assert_eq!(Ok(expected), res),
+                            None => assert!(res.is_err()),
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_get_phase_against_reference() {
+        for number in 1..30 {
+            for offset in 0..100 {
+                assert_eq!(
+                    reference::get_phase(number, offset),
+                    get_phase(number, offset),
+                    "bus {} at offset {}",
+                    number,
+                    offset
+                );
+            }
+        }
+    }
+}
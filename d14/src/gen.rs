@@ -0,0 +1,33 @@
+//! Random inputs for day 14.
+//!
+//! Masks have at most 9 floating bits, like the real inputs, so that part 2 stays tractable.
+
+use aoc_2020::gen::{rng, Generated, Rng, SliceRandom};
+
+/// Generates a program of about `size` lines.
+pub fn generate(size: usize, seed: u64) -> Generated {
+    let mut rng = rng(seed);
+    let mut input = Vec::with_capacity(size + 5);
+    let indexes = (0..36).collect::<Vec<usize>>();
+
+    while input.len() < size.max(1) {
+        let mut mask = (0..36)
+            .map(|_| if rng.gen_bool(0.5) { '1' } else { '0' })
+            .collect::<Vec<char>>();
+        let floating = rng.gen_range(0..=9);
+        for &idx in indexes.choose_multiple(&mut rng, floating) {
+            mask[idx] = 'X';
+        }
+        input.push(format!("mask = {}", mask.iter().collect::<String>()));
+
+        for _ in 0..rng.gen_range(1..=5) {
+            input.push(format!(
+                "mem[{}] = {}",
+                rng.gen_range(0..1 << 16),
+                rng.gen_range(0..1u64 << 36)
+            ));
+        }
+    }
+
+    Generated::new(input)
+}
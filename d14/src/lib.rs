@@ -0,0 +1,375 @@
+//! Day 14: Docking Data
+
+use std::collections::HashMap;
+use std::fmt::{self, Display};
+
+use aoc_2020::bitset::SmallBitSet;
+use aoc_2020::explain::{NoTrace, Step, Trace};
+use aoc_2020::parse::{literal, parse_line, take_while1, uint, Parser};
+use aoc_2020::Diagnostic;
+
+pub mod gen;
+pub mod reference;
+
+/// Number of bits of the values and addresses.
+pub const WIDTH: usize = 36;
+
+/// A bitmask: bits set in `active_bits` are forced to their value in `value`.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct Mask {
+    pub value: u64,
+    pub active_bits: u64,
+}
+
+impl Display for Mask {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for bit in (0..WIDTH).rev() {
+            let c = if self.active_bits & (1 << bit) == 0 {
+                'X'
+            } else if self.value & (1 << bit) == 0 {
+                '0'
+            } else {
+                '1'
+            };
+            write!(f, "{}", c)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A line of the initialization program.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Operation {
+    Mask(Mask),
+    Memory(u64, u64),
+}
+
+impl Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Operation::Mask(m) => write!(f, "mask = {}", m),
+            Operation::Memory(addr, val) => write!(f, "mem[{}] = {}", addr, val),
+        }
+    }
+}
+
+/// Parses a `WIDTH`-bit mask such as `XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X`.
+pub fn parse_mask(mask_str: &str) -> Mask {
+    let mut value: u64 = 0;
+    let mut active_bits: u64 = 0;
+
+    for c in mask_str.chars() {
+        value <<= 1;
+        active_bits <<= 1;
+
+        match c {
+            '0' => {
+                active_bits |= 1;
+            }
+            '1' => {
+                value |= 1;
+                active_bits |= 1;
+            }
+            _ => {}
+        }
+    }
+
+    Mask { value, active_bits }
+}
+
+/// Overwrites the active bits of `value` with the ones of the mask.
+pub fn apply_mask_xor(value: u64, mask: &Mask) -> u64 {
+    let real_mask = (value ^ mask.value) & mask.active_bits;
+
+    value ^ real_mask
+}
+
+/// Sets the bits of `value` that are set in the mask.
+pub fn apply_mask_or(value: u64, mask: &Mask) -> u64 {
+    value | mask.value
+}
+
+/// Addresses written to when writing to `addr` with the version 2 decoder, where the
+/// floating bits of the mask take all possible values.
+pub fn get_addresses(addr: u64, mask: &Mask) -> Vec<u64> {
+    let floating = SmallBitSet::from_bits(mask.active_bits).complement(WIDTH);
+    let addr = apply_mask_or(addr, mask) & !floating.bits();
+
+    floating.subsets().map(|bits| addr | bits.bits()).collect()
+}
+
+/// Parses the line at index `idx` of the program.
+pub fn parse_op(idx: usize, op_str: &str) -> Result<Operation, Diagnostic> {
+    let mask = (
+        literal("mask"),
+        literal(" = "),
+        take_while1(|c| matches!(c, '0' | '1' | 'X'), "a mask bit").try_map(|m| {
+            if m.len() == WIDTH {
+                Ok(parse_mask(&m))
+            } else {
+                Err(format!("expected {} mask bits, found {}", WIDTH, m.len()))
+            }
+        }),
+    )
+        .map(|(_, _, m)| Operation::Mask(m));
+    let memory = (
+        literal("mem"),
+        literal("["),
+        uint::<u64>(),
+        literal("] = "),
+        uint::<u64>(),
+    )
+        .map(|(_, _, addr, _, val)| Operation::Memory(addr, val));
+
+    parse_line(&mask.or(memory), idx, op_str)
+}
+
+/// Parses the initialization program.
+pub fn parse(input: &[String]) -> Result<Vec<Operation>, Diagnostic> {
+    input
+        .iter()
+        .enumerate()
+        .map(|(idx, op)| parse_op(idx, op))
+        .collect()
+}
+
+/// Sum of the values in memory when the mask applies to values.
+pub fn part1(ops: &[Operation]) -> u64 {
+    part1_traced(ops, &mut NoTrace)
+}
+
+/// Same as `part1`, tracing each write.
+pub fn part1_traced(ops: &[Operation], trace: &mut impl Trace) -> u64 {
+    let mut mem: HashMap<u64, u64> = HashMap::new();
+    let mut mask = Mask {
+        value: 0,
+        active_bits: 0,
+    };
+
+    for op in ops {
+        match op {
+            Operation::Mask(m) => mask = *m,
+            Operation::Memory(addr, val) => {
+                let masked = apply_mask_xor(*val, &mask);
+                mem.insert(*addr, masked);
+                trace.step(|| {
+                    Step::new(1, "write")
+                        .with("addr", addr)
+                        .with("value", val)
+                        .with("masked", masked)
+                });
+            }
+        };
+    }
+    mem.values().sum()
+}
+
+/// Sum of the values in memory when the mask applies to addresses.
+pub fn part2(ops: &[Operation]) -> u64 {
+    part2_traced(ops, &mut NoTrace)
+}
+
+/// Same as `part2`, tracing each write.
+pub fn part2_traced(ops: &[Operation], trace: &mut impl Trace) -> u64 {
+    let mut mem: HashMap<u64, u64> = HashMap::new();
+    let mut mask = Mask {
+        value: 0,
+        active_bits: 0,
+    };
+
+    for op in ops {
+        match op {
+            Operation::Mask(m) => mask = *m,
+            Operation::Memory(addr, val) => {
+                let addresses = get_addresses(*addr, &mask);
+                trace.step(|| {
+                    Step::new(2, "write")
+                        .with("addr", addr)
+                        .with("value", val)
+                        .with("addresses", addresses.len())
+                });
+                for addr in addresses {
+                    mem.insert(addr, *val);
+                }
+            }
+        };
+    }
+
+    mem.values().sum()
+}
+
+/// Solves both parts of the puzzle.
+pub fn solve(input: &[String]) -> Result<(impl Display, impl Display), Diagnostic> {
+    let ops = parse(input)?;
+    let p1 = part1(&ops);
+    let p2 = part2(&ops);
+
+    Ok((p1, p2))
+}
+
+/// The steps of the solution.
+pub fn explain(input: &[String]) -> Result<Vec<Step>, Diagnostic> {
+    let ops = parse(input)?;
+    let mut steps = Vec::new();
+    part1_traced(&ops, &mut steps);
+    part2_traced(&ops, &mut steps);
+
+    Ok(steps)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use proptest::prelude::*;
+
+    use crate::{
+        apply_mask_xor, explain, get_addresses, parse, parse_mask, part1, part2, Mask, Operation,
+    };
+
+    #[test]
+    fn test_parse_mask() {
+        let expected = Mask {
+            value: 0b000000000000000000000000000001000000,
+            active_bits: 0b000000000000000000000000000001000010,
+        };
+
+        assert_eq!(expected, parse_mask("XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X"));
+    }
+
+    #[test]
+    fn test_apply_mask() {
+        let mask = parse_mask("XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X");
+        assert_eq!(73, apply_mask_xor(11, &mask));
+        assert_eq!(101, apply_mask_xor(101, &mask));
+        assert_eq!(64, apply_mask_xor(64, &mask));
+    }
+
+    #[test]
+    fn test_get_addresses() {
+        let mask = parse_mask("000000000000000000000000000000X1001X");
+        let mut addresses = get_addresses(42, &mask);
+        addresses.sort();
+        assert_eq!(vec![26, 27, 58, 59], addresses);
+
+        let mask = parse_mask("00000000000000000000000000000000X0XX");
+        let mut addresses = get_addresses(26, &mask);
+        addresses.sort();
+        assert_eq!(vec![16, 17, 18, 19, 24, 25, 26, 27], addresses);
+    }
+
+    #[test]
+    fn test_part1() {
+        let input = "mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X
+mem[8] = 11
+mem[7] = 101
+mem[8] = 0";
+
+        let input = input.split('\n').map(String::from).collect::<Vec<String>>();
+        let ops = parse(&input).unwrap();
+
+        assert_eq!(165, part1(&ops));
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = "mask = 000000000000000000000000000000X1001X
+mem[42] = 100
+mask = 00000000000000000000000000000000X0XX
+mem[26] = 1";
+
+        let input = input.split('\n').map(String::from).collect::<Vec<String>>();
+        let ops = parse(&input).unwrap();
+
+        assert_eq!(208, part2(&ops));
+    }
+
+    #[test]
+    fn test_explain() {
+        let input = "mask = 000000000000000000000000000000X1001X
+mem[42] = 100
+mask = 00000000000000000000000000000000X0XX
+mem[26] = 1";
+
+        let input = input.split('\n').map(String::from).collect::<Vec<String>>();
+        let steps = explain(&input).unwrap();
+
+        assert_eq!(4, steps.len());
+        assert_eq!(
+            "[part 1] write addr=42 value=100 masked=50",
+            steps[0].to_string()
+        );
+        assert_eq!(
+            "[part 2] write addr=26 value=1 addresses=8",
+            steps[3].to_string()
+        );
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = "mask = 000000000000000000000000000000X1001X
+mem[42] = 100000000000000000000
+mem 26 = 1";
+
+        let input = input.split('\n').map(String::from).collect::<Vec<String>>();
+
+        let err = parse(&input).err().unwrap();
+        assert_eq!(2, err.line);
+        assert_eq!(10..31, err.columns);
+
+        let err = parse(&input[2..]).err().unwrap();
+        assert_eq!(1, err.line);
+        assert_eq!(3..4, err.columns);
+        assert_eq!("expected `[`", err.message);
+
+        let err = parse(&[String::from("max = 1")]).err().unwrap();
+        assert_eq!(0..1, err.columns);
+        assert_eq!("expected `mask`", err.message);
+
+        let err = parse(&[String::from("mask = X1001X")]).err().unwrap();
+        assert_eq!(7..13, err.columns);
+        assert_eq!("expected 36 mask bits, found 6", err.message);
+    }
+
+    fn operation() -> impl Strategy<Value = Operation> {
+        prop_oneof![
+            "[01X]{36}".prop_map(|m| Operation::Mask(parse_mask(&m))),
+            (any::<u64>(), any::<u64>()).prop_map(|(addr, val)| Operation::Memory(addr, val)),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn prop_operation_round_trip(ops in prop::collection::vec(operation(), 0..50)) {
+            let input = ops.iter().map(|o| o.to_string()).collect::<Vec<String>>();
+
+            prop_assert_eq!(ops, parse(&input).unwrap());
+        }
+
+        #[test]
+        fn prop_apply_mask(mask in "[01X]{36}", value in 0u64..(1 << 36)) {
+            let mask = parse_mask(&mask);
+            let res = apply_mask_xor(value, &mask);
+
+            prop_assert_eq!(mask.value, res & mask.active_bits);
+            prop_assert_eq!(value & !mask.active_bits, res & !mask.active_bits);
+        }
+
+        #[test]
+        fn prop_get_addresses(mask in "[01]{27}[01X]{9}", addr in 0u64..(1 << 36)) {
+            let mask = parse_mask(&mask);
+            let floating = !mask.active_bits & ((1 << 36) - 1);
+            let addresses = get_addresses(addr, &mask);
+
+            prop_assert_eq!(1 << floating.count_ones(), addresses.len());
+            prop_assert_eq!(
+                addresses.len(),
+                addresses.iter().collect::<HashSet<&u64>>().len()
+            );
+            for a in addresses {
+                prop_assert_eq!((addr | mask.value) & !floating, a & !floating);
+            }
+        }
+    }
+}
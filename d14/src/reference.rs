@@ -0,0 +1,127 @@
+//! Slow but straightforward solutions, to check the fast ones against.
+//!
+//! Masks are handled as their 36 characters rather than as bits.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use aoc_2020::Diagnostic;
+
+use crate::{parse, Operation};
+
+fn to_bits(v: u64) -> Vec<char> {
+    format!("{:036b}", v).chars().collect()
+}
+
+fn from_bits(bits: &[char]) -> u64 {
+    u64::from_str_radix(&bits.iter().collect::<String>(), 2).unwrap()
+}
+
+/// Applies a version 1 mask to `value`.
+pub fn apply_mask(value: u64, mask: &str) -> u64 {
+    let bits = to_bits(value)
+        .iter()
+        .zip(mask.chars())
+        .map(|(&b, m)| if m == 'X' { b } else { m })
+        .collect::<Vec<char>>();
+
+    from_bits(&bits)
+}
+
+/// Expands the floating bits one at a time, from the most significant.
+pub fn get_addresses(addr: u64, mask: &str) -> Vec<u64> {
+    let mut addresses = vec![Vec::new()];
+
+    for (b, m) in to_bits(addr).into_iter().zip(mask.chars()) {
+        let choices = match m {
+            '0' => vec![b],
+            '1' => vec!['1'],
+            _ => vec!['0', '1'],
+        };
+
+        addresses = addresses
+            .iter()
+            .flat_map(|a| {
+                choices.iter().map(move |&c| {
+                    let mut a = a.clone();
+                    a.push(c);
+                    a
+                })
+            })
+            .collect();
+    }
+
+    addresses.iter().map(|a| from_bits(a)).collect()
+}
+
+fn run(ops: &[Operation], version: u8) -> u64 {
+    let mut mem = HashMap::new();
+    let mut mask = "X".repeat(36);
+
+    for op in ops {
+        match op {
+            Operation::Mask(m) => mask = m.to_string(),
+            Operation::Memory(addr, val) if version == 1 => {
+                mem.insert(*addr, apply_mask(*val, &mask));
+            }
+            Operation::Memory(addr, val) => {
+                for a in get_addresses(*addr, &mask) {
+                    mem.insert(a, *val);
+                }
+            }
+        }
+    }
+
+    mem.values().sum()
+}
+
+pub fn solve(input: &[String]) -> Result<(impl Display, impl Display), Diagnostic> {
+    let ops = parse(input)?;
+
+    Ok((run(&ops, 1), run(&ops, 2)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use crate::gen::generate;
+    use crate::{get_addresses, parse_mask, reference, solve};
+
+    #[test]
+    fn test_against_reference() {
+        for seed in 0..50 {
+            let gen = generate(40, seed);
+            let (p1, p2) = solve(&gen.input).unwrap();
+            let (r1, r2) = reference::solve(&gen.input).unwrap();
+
+            assert_eq!(r1.to_string(), p1.to_string(), "seed {}", seed);
+            assert_eq!(r2.to_string(), p2.to_string(), "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn test_get_addresses_against_reference() {
+        for seed in 0..50 {
+            let gen = generate(40, seed);
+
+            let mut mask = String::new();
+            for l in &gen.input {
+                if let Some(m) = l.strip_prefix("mask = ") {
+                    mask = String::from(m);
+                    continue;
+                }
+
+                let addr = l[4..l.find(']').unwrap()].parse().unwrap();
+                let expected = reference::get_addresses(addr, &mask)
+                    .into_iter()
+                    .collect::<BTreeSet<u64>>();
+                let res = get_addresses(addr, &parse_mask(&mask))
+                    .into_iter()
+                    .collect::<BTreeSet<u64>>();
+
+                assert_eq!(expected, res, "mem[{}] with mask {}", addr, mask);
+            }
+        }
+    }
+}
@@ -0,0 +1,241 @@
+//! Day 15: Rambunctious Recitation
+
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use aoc_2020::explain::{NoTrace, Step, Trace};
+use aoc_2020::parse::{literal, parse_line, separated, uint, Parser};
+use aoc_2020::{Diagnostic, Implementation};
+
+/// Number spoken at `turn` when starting with `input`.
+pub fn get_number_at_turn(input: &[u64], turn: usize) -> usize {
+    let mut numbers = HashMap::new();
+    let mut current_turn = input.len();
+
+    for (t, &n) in input.iter().enumerate() {
+        current_turn = t + 1;
+        numbers.insert(n as usize, (current_turn, current_turn));
+    }
+
+    let mut current_number = input[current_turn - 1] as usize;
+
+    while current_turn < turn {
+        current_turn += 1;
+
+        current_number = match numbers.get(&current_number) {
+            Some((t1, t2)) => t2 - t1,
+            None => 0,
+        };
+
+        let last_turn = match numbers.get(&current_number) {
+            Some((_, t)) => *t,
+            None => current_turn,
+        };
+
+        numbers.insert(current_number, (last_turn, current_turn));
+    }
+
+    current_number
+}
+
+/// Same as `get_number_at_turn`, remembering the last turn of each number in a `Vec` indexed
+/// by the number instead of a `HashMap`.
+pub fn get_number_at_turn_vec(input: &[u64], turn: usize) -> usize {
+    get_number_at_turn_vec_traced(input, turn, 1, &mut NoTrace)
+}
+
+/// Same as `get_number_at_turn_vec`, tracing the number spoken every hundredth of the turns as
+/// steps of `part`.
+pub fn get_number_at_turn_vec_traced(
+    input: &[u64],
+    turn: usize,
+    part: u8,
+    trace: &mut impl Trace,
+) -> usize {
+    let every = (turn / 100).max(1);
+    // Spoken numbers are differences of turns, so they're always below `turn`: only starting
+    // numbers can be larger, and those go in a `HashMap`.
+    let mut last_turns = vec![0u32; turn];
+    let mut large_starts = HashMap::new();
+    let mut last_turn_of = |n: usize, t: u32| match last_turns.get_mut(n) {
+        Some(last) => std::mem::replace(last, t),
+        None => large_starts.insert(n, t).unwrap_or(0),
+    };
+
+    for (t, &n) in input.iter().enumerate().take(input.len() - 1) {
+        last_turn_of(n as usize, t as u32 + 1);
+    }
+
+    let mut current_number = input[input.len() - 1] as usize;
+
+    for current_turn in input.len()..turn {
+        let last_turn = last_turn_of(current_number, current_turn as u32) as usize;
+
+        current_number = if last_turn == 0 {
+            0
+        } else {
+            current_turn - last_turn
+        };
+
+        if (current_turn + 1) % every == 0 {
+            trace.step(|| {
+                Step::new(part, "turn")
+                    .with("turn", current_turn + 1)
+                    .with("number", current_number)
+            });
+        }
+    }
+
+    current_number
+}
+
+/// Parses the starting numbers, which must fit in 32 bits.
+pub fn parse(input: &[String]) -> Result<Vec<u64>, Diagnostic> {
+    let number = uint::<u32>().map(u64::from);
+
+    match input.first() {
+        Some(line) => parse_line(&separated(number, literal(",")), 0, line),
+        None => Err(Diagnostic::new(
+            0,
+            "",
+            0..0,
+            "expected the starting numbers",
+        )),
+    }
+}
+
+pub const IMPLEMENTATIONS: &[Implementation] = &[
+    Implementation {
+        name: "hashmap",
+        part: 1,
+        solve: |input| Ok(get_number_at_turn(&parse(input)?, 2020).to_string()),
+    },
+    Implementation {
+        name: "vec",
+        part: 1,
+        solve: |input| Ok(get_number_at_turn_vec(&parse(input)?, 2020).to_string()),
+    },
+    Implementation {
+        name: "hashmap",
+        part: 2,
+        solve: |input| Ok(get_number_at_turn(&parse(input)?, 30_000_000).to_string()),
+    },
+    Implementation {
+        name: "vec",
+        part: 2,
+        solve: |input| Ok(get_number_at_turn_vec(&parse(input)?, 30_000_000).to_string()),
+    },
+];
+
+/// Solves both parts of the puzzle.
+pub fn solve(input: &[String]) -> Result<(impl Display, impl Display), Diagnostic> {
+    let input = parse(input)?;
+
+    let p1 = get_number_at_turn(&input, 2020);
+    let p2 = get_number_at_turn(&input, 30000000);
+
+    Ok((p1, p2))
+}
+
+/// The steps of the solution.
+pub fn explain(input: &[String]) -> Result<Vec<Step>, Diagnostic> {
+    let input = parse(input)?;
+    let mut steps = Vec::new();
+    get_number_at_turn_vec_traced(&input, 2020, 1, &mut steps);
+    get_number_at_turn_vec_traced(&input, 30_000_000, 2, &mut steps);
+
+    Ok(steps)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{get_number_at_turn, get_number_at_turn_vec, get_number_at_turn_vec_traced, parse};
+
+    #[test]
+    fn test_get_number_at_turn() {
+        assert_eq!(4, get_number_at_turn(&[0, 3, 6], 9));
+        assert_eq!(0, get_number_at_turn(&[0, 3, 6], 10));
+        assert_eq!(436, get_number_at_turn(&[0, 3, 6], 2020));
+        assert_eq!(1, get_number_at_turn(&[1, 3, 2], 2020));
+        assert_eq!(10, get_number_at_turn(&[2, 1, 3], 2020));
+        assert_eq!(27, get_number_at_turn(&[1, 2, 3], 2020));
+        assert_eq!(78, get_number_at_turn(&[2, 3, 1], 2020));
+        assert_eq!(438, get_number_at_turn(&[3, 2, 1], 2020));
+        assert_eq!(1836, get_number_at_turn(&[3, 1, 2], 2020));
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Ok(vec![0, 3, 6]), parse(&[String::from("0,3,6")]));
+
+        let err = parse(&[String::from("0,3,a")]).unwrap_err();
+        assert_eq!((1, 4..5), (err.line, err.columns));
+        let err = parse(&[String::from("0,4294967296000")]).unwrap_err();
+        assert_eq!((1, 2..15), (err.line, err.columns));
+        assert_eq!(
+            Ok(vec![0, 4294967295]),
+            parse(&[String::from("0,4294967295")])
+        );
+        assert!(parse(&[]).is_err());
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(203, get_number_at_turn(&[0, 5, 4, 1, 10, 14, 7], 2020));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(
+            9007186,
+            get_number_at_turn(&[0, 5, 4, 1, 10, 14, 7], 30_000_000)
+        );
+    }
+
+    #[test]
+    fn test_large_starting_numbers() {
+        for start in &[[0, 4294967296000], [4294967296000, 0]] {
+            for turn in 1..50 {
+                assert_eq!(
+                    get_number_at_turn(start, turn),
+                    get_number_at_turn_vec(start, turn),
+                    "{:?} at turn {}",
+                    start,
+                    turn
+                );
+            }
+        }
+        assert_eq!(
+            1,
+            get_number_at_turn_vec(&[4294967296000, 4294967296000], 3)
+        );
+    }
+
+    #[test]
+    fn test_get_number_at_turn_vec_traced() {
+        let mut steps = Vec::new();
+
+        assert_eq!(
+            436,
+            get_number_at_turn_vec_traced(&[0, 3, 6], 2020, 1, &mut steps)
+        );
+        assert_eq!(101, steps.len());
+        assert_eq!("[part 1] turn turn=20 number=2", steps[0].to_string());
+        assert_eq!(Some("436"), steps[100].get("number"));
+    }
+
+    #[test]
+    fn test_get_number_at_turn_vec() {
+        for start in &[[0, 3, 6], [1, 3, 2], [2, 1, 3], [3, 1, 2]] {
+            for turn in 3..200 {
+                assert_eq!(
+                    get_number_at_turn(start, turn),
+                    get_number_at_turn_vec(start, turn),
+                    "{:?} at turn {}",
+                    start,
+                    turn
+                );
+            }
+        }
+    }
+}
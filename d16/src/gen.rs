@@ -0,0 +1,132 @@
+//! Random inputs for day 16.
+//!
+//! Field `i` accepts the values from `10 * i` up to `10 * size + 9`, and one nearby ticket has a
+//! value below `10 * (i + 1)` in the column of field `i`. Column of field `i` can then only be
+//! one of the fields `0..=i`, which makes the deduction unique. Invalid tickets get a single
+//! value above every range, and the departure fields are among the first six so that their
+//! product fits in a `u64`.
+
+use std::collections::HashSet;
+
+use aoc_2020::gen::{rng, word, GenRng, Generated, Rng, SliceRandom};
+
+const DEPARTURE_FIELDS: usize = 6;
+
+fn names(rng: &mut GenRng, n: usize) -> Vec<String> {
+    let mut seen = HashSet::new();
+
+    (0..n)
+        .map(|i| loop {
+            let name = if i < DEPARTURE_FIELDS {
+                format!("departure {}", word(rng, 2))
+            } else if rng.gen_bool(0.5) {
+                format!("{} {}", word(rng, 2), word(rng, 1))
+            } else {
+                word(rng, 3)
+            };
+
+            if seen.insert(name.clone()) {
+                break name;
+            }
+        })
+        .collect()
+}
+
+fn ticket(values: &[u32]) -> String {
+    values
+        .iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+/// Generates notes with `size` fields and about twice as many nearby tickets.
+pub fn generate(size: usize, seed: u64) -> Generated {
+    let mut rng = rng(seed);
+    let size = size.max(1);
+    let max = 10 * size as u32 + 9;
+
+    let names = names(&mut rng, size);
+
+    // Field of each column.
+    let mut columns = (0..size).collect::<Vec<usize>>();
+    columns.shuffle(&mut rng);
+
+    let mut input = (0..size)
+        .map(|i| {
+            let lo = 10 * i as u32;
+            let split = rng.gen_range(lo..max);
+            format!("{}: {}-{} or {}-{}", names[i], lo, split, split + 1, max)
+        })
+        .collect::<Vec<String>>();
+    input.shuffle(&mut rng);
+
+    let mine = columns
+        .iter()
+        .map(|&f| {
+            let lo = 10 * f as u32;
+            if f < DEPARTURE_FIELDS {
+                rng.gen_range(lo..lo + 10)
+            } else {
+                rng.gen_range(lo..=max)
+            }
+        })
+        .collect::<Vec<u32>>();
+    let part2 = columns
+        .iter()
+        .zip(&mine)
+        .filter(|(&f, _)| f < DEPARTURE_FIELDS)
+        .map(|(_, &v)| v as u64)
+        .product::<u64>();
+
+    let mut nearby = Vec::new();
+    let mut part1 = 0;
+
+    nearby.push(
+        columns
+            .iter()
+            .map(|&f| rng.gen_range(10 * f as u32..10 * f as u32 + 10))
+            .collect::<Vec<u32>>(),
+    );
+    for _ in 0..2 * size + 10 {
+        let mut values = columns
+            .iter()
+            .map(|&f| rng.gen_range(10 * f as u32..=max))
+            .collect::<Vec<u32>>();
+
+        if rng.gen_bool(0.2) {
+            let bad = rng.gen_range(max + 1..max + 1000);
+            values[rng.gen_range(0..size)] = bad;
+            part1 += bad;
+        }
+
+        nearby.push(values);
+    }
+    nearby.shuffle(&mut rng);
+
+    input.push(String::new());
+    input.push(String::from("your ticket:"));
+    input.push(ticket(&mine));
+    input.push(String::new());
+    input.push(String::from("nearby tickets:"));
+    input.extend(nearby.iter().map(|t| ticket(t)));
+
+    Generated::with_answers(input, part1, part2)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::gen::generate;
+    use crate::solve;
+
+    #[test]
+    fn test_generate() {
+        for seed in 0..10 {
+            let gen = generate(20, seed);
+            let (p1, p2) = solve(&gen.input).unwrap();
+
+            assert_eq!(gen.part1, Some(p1.to_string()));
+            assert_eq!(gen.part2, Some(p2.to_string()));
+        }
+    }
+}
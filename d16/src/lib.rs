@@ -0,0 +1,410 @@
+//! Day 16: Ticket Translation
+
+use std::fmt::Display;
+
+use aoc_2020::assign::{self, AssignError, Assignment};
+use aoc_2020::bitset::BitSet;
+use aoc_2020::explain::{NoTrace, Step, Trace};
+use aoc_2020::interval::IntervalSet;
+use aoc_2020::parse::{literal, parse_line, separated, take_while1, uint, Parser};
+use aoc_2020::Diagnostic;
+
+pub mod gen;
+
+/// A ticket field and its valid values.
+#[derive(Debug, Eq, PartialEq, Hash)]
+pub struct Field {
+    pub name: String,
+    pub ranges: IntervalSet<u32>,
+}
+
+impl Field {
+    /// Whether `val` falls in one of the ranges of the field.
+    pub fn is_valid(&self, val: u32) -> bool {
+        self.ranges.contains(val)
+    }
+}
+
+/// The values of a ticket, in column order.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Ticket {
+    pub values: Vec<u32>,
+}
+
+/// The notes: field rules, our ticket and nearby tickets.
+#[derive(Debug)]
+pub struct PuzzleData {
+    pub fields: Vec<Field>,
+    pub ticket: Ticket,
+    pub other_tickets: Vec<Ticket>,
+}
+
+/// Parses the notes.
+pub fn parse(input: &[String]) -> Result<PuzzleData, Diagnostic> {
+    let range = (uint::<u32>(), literal("-"), uint::<u32>()).map(|(lo, _, hi)| lo..=hi);
+    let field = (
+        take_while1(|c| c != ':', "a field name"),
+        literal(": "),
+        separated(range, literal(" or ")),
+    )
+        .map(|(name, _, ranges)| Field {
+            name,
+            ranges: ranges.into_iter().collect(),
+        });
+    let values = separated(uint::<u32>(), literal(",")).map(|values| Ticket { values });
+
+    let mut fields = Vec::new();
+    let mut ticket = Ticket { values: Vec::new() };
+    let mut other_tickets = Vec::new();
+
+    let mut is_my_ticket = false;
+    let mut is_other_ticket = false;
+
+    for (idx, i) in input.iter().enumerate() {
+        if i.is_empty() {
+            continue;
+        }
+
+        if is_other_ticket {
+            let other = parse_line(&values, idx, i)?;
+            if other.values.len() != ticket.values.len() {
+                return Err(Diagnostic::at_line(
+                    idx,
+                    i,
+                    format!(
+                        "expected {} values, as on your ticket, found {}",
+                        ticket.values.len(),
+                        other.values.len()
+                    ),
+                ));
+            }
+            other_tickets.push(other);
+
+            continue;
+        }
+
+        if is_my_ticket {
+            ticket = parse_line(&values, idx, i)?;
+
+            is_my_ticket = false;
+
+            continue;
+        }
+
+        if i == "your ticket:" {
+            is_my_ticket = true;
+        } else if i == "nearby tickets:" {
+            is_other_ticket = true;
+        } else {
+            fields.push(parse_line(&field, idx, i)?);
+        }
+    }
+
+    Ok(PuzzleData {
+        fields,
+        ticket,
+        other_tickets,
+    })
+}
+
+/// Nearby tickets whose values are all valid for some field, along with the sum of the
+/// values that aren't valid for any field.
+pub fn get_valid_tickets(input: &PuzzleData) -> (Vec<&Ticket>, u32) {
+    let values = input
+        .fields
+        .iter()
+        .fold(IntervalSet::new(), |values, f| values.union(&f.ranges));
+    let mut valid_tickets = Vec::new();
+
+    let mut bad_values = Vec::new();
+    for t in &input.other_tickets {
+        let mut is_valid = true;
+        for i in &t.values {
+            if !values.contains(*i) {
+                is_valid = false;
+                bad_values.push(*i);
+            }
+        }
+
+        if is_valid {
+            valid_tickets.push(t);
+        }
+    }
+
+    (valid_tickets, bad_values.iter().sum::<u32>())
+}
+
+/// Names of the fields, in column order, deduced from the values of `valid_tickets`, if they
+/// can be told apart.
+pub fn get_field_names(
+    input: &PuzzleData,
+    valid_tickets: &[&Ticket],
+) -> Result<Vec<String>, AssignError> {
+    get_field_names_traced(input, valid_tickets, &mut NoTrace)
+}
+
+/// Same as `get_field_names`, tracing the columns resolved by elimination, then the ones
+/// resolved by matching.
+pub fn get_field_names_traced(
+    input: &PuzzleData,
+    valid_tickets: &[&Ticket],
+    trace: &mut impl Trace,
+) -> Result<Vec<String>, AssignError> {
+    // The fields that fit every value of each column.
+    let candidates = (0..input.ticket.values.len())
+        .map(|col| {
+            input
+                .fields
+                .iter()
+                .enumerate()
+                .filter(|(_, f)| valid_tickets.iter().all(|t| f.is_valid(t.values[col])))
+                .map(|(idx, _)| idx)
+                .collect::<BitSet>()
+        })
+        .collect::<Vec<BitSet>>();
+    trace.step(|| {
+        Step::new(2, "candidates")
+            .with("columns", candidates.len())
+            .with(
+                "candidates",
+                candidates.iter().map(BitSet::len).sum::<usize>(),
+            )
+    });
+
+    let Assignment {
+        slots: fields,
+        eliminated,
+    } = assign::assign(&candidates)?;
+
+    let matched = (0..fields.len()).filter(|col| !eliminated.contains(col));
+    for (col, by) in eliminated
+        .iter()
+        .map(|&col| (col, "elimination"))
+        .chain(matched.map(|col| (col, "matching")))
+    {
+        trace.step(|| {
+            Step::new(2, "resolved")
+                .with("column", col)
+                .with("field", &input.fields[fields[col]].name)
+                .with("by", by)
+        });
+    }
+
+    Ok(fields
+        .iter()
+        .map(|&f| input.fields[f].name.clone())
+        .collect())
+}
+
+/// Points at the first value of our ticket in a column whose field can't be told apart.
+fn undecidable(input: &[String], e: AssignError) -> Diagnostic {
+    let message = format!("can't tell the fields apart: {}", e);
+    let col = match &e {
+        AssignError::Impossible(cols) | AssignError::Ambiguous(cols) => cols[0],
+    };
+
+    match input.iter().position(|l| l == "your ticket:") {
+        Some(idx) if idx + 1 < input.len() => {
+            let line = &input[idx + 1];
+            match line.split(',').nth(col) {
+                Some(value) => Diagnostic::at_token(idx + 1, line, value, message),
+                None => Diagnostic::at_line(idx + 1, line, message),
+            }
+        }
+        _ => Diagnostic::new(input.len(), "", 0..0, message),
+    }
+}
+
+/// Solves both parts of the puzzle.
+pub fn solve(input: &[String]) -> Result<(impl Display, impl Display), Diagnostic> {
+    let notes = parse(input)?;
+    let (mut valid_tickets, sum_of_bad_values) = get_valid_tickets(&notes);
+    valid_tickets.push(&notes.ticket);
+    let field_names = get_field_names(&notes, &valid_tickets).map_err(|e| undecidable(input, e))?;
+
+    let p2 = field_names
+        .iter()
+        .enumerate()
+        .map(|(idx, name)| {
+            if name.starts_with("departure") {
+                notes.ticket.values[idx] as u64
+            } else {
+                1
+            }
+        })
+        .product::<u64>();
+    Ok((sum_of_bad_values, p2))
+}
+
+/// The steps of the solution.
+pub fn explain(input: &[String]) -> Result<Vec<Step>, Diagnostic> {
+    let notes = parse(input)?;
+    let (mut valid_tickets, _) = get_valid_tickets(&notes);
+    valid_tickets.push(&notes.ticket);
+
+    let mut steps = Vec::new();
+    get_field_names_traced(&notes, &valid_tickets, &mut steps)
+        .map_err(|e| undecidable(input, e))?;
+
+    Ok(steps)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{explain, get_field_names, get_valid_tickets, parse, solve, Ticket};
+
+    #[test]
+    fn test_p1() {
+        let input = "class: 1-3 or 5-7
+row: 6-11 or 33-44
+seat: 13-40 or 45-50
+
+your ticket:
+7,1,14
+
+nearby tickets:
+7,3,47
+40,4,50
+55,2,20
+38,6,12";
+
+        let input = input.split('\n').map(String::from).collect::<Vec<String>>();
+        let values = parse(&input).unwrap();
+        let (valid_tickets, res) = get_valid_tickets(&values);
+
+        assert_eq!(
+            vec![&Ticket {
+                values: vec![7, 3, 47]
+            }],
+            valid_tickets
+        );
+        assert_eq!(71, res);
+    }
+
+    #[test]
+    fn test_large_ranges() {
+        let input = "class: 1-3 or 5-4000000000
+row: 6-11 or 33-44 or 50-60
+
+your ticket:
+7,1
+
+nearby tickets:
+3999999999,5
+4000000001,55";
+
+        let input = input.split('\n').map(String::from).collect::<Vec<String>>();
+        let values = parse(&input).unwrap();
+        let (valid_tickets, res) = get_valid_tickets(&values);
+
+        assert_eq!(vec![&values.other_tickets[0]], valid_tickets);
+        assert_eq!(4000000001, res);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = "class: 1-3 or 5-x
+
+your ticket:
+7,1";
+
+        let input = input.split('\n').map(String::from).collect::<Vec<String>>();
+        let err = parse(&input).unwrap_err();
+        assert_eq!(1, err.line);
+        assert_eq!(16..17, err.columns);
+
+        let notes = parse(&input[1..]).unwrap();
+        assert_eq!(vec![7, 1], notes.ticket.values);
+
+        let input = "class: 1-3 or 5-7
+
+your ticket:
+7,1
+
+nearby tickets:
+7,3
+7";
+
+        let input = input.split('\n').map(String::from).collect::<Vec<String>>();
+        let err = parse(&input).unwrap_err();
+        assert_eq!((8, 0..1), (err.line, err.columns));
+        assert_eq!("expected 2 values, as on your ticket, found 1", err.message);
+    }
+
+    #[test]
+    fn test_p2() {
+        let input = "class: 0-1 or 4-19
+row: 0-5 or 8-19
+seat: 0-13 or 16-19
+
+your ticket:
+11,12,13
+
+nearby tickets:
+3,9,18
+15,1,5
+5,14,9";
+
+        let input = input.split('\n').map(String::from).collect::<Vec<String>>();
+        let values = parse(&input).unwrap();
+        let (valid_tickets, _) = get_valid_tickets(&values);
+        let field_names = get_field_names(&values, &valid_tickets).unwrap();
+
+        assert_eq!(vec!["row", "class", "seat"], field_names);
+    }
+
+    #[test]
+    fn test_undecidable() {
+        let input = "class: 0-1 or 4-19
+row: 0-5 or 8-19
+seat: 0-13 or 16-19
+
+your ticket:
+11,12,13
+
+nearby tickets:
+3,9,18";
+
+        let input = input.split('\n').map(String::from).collect::<Vec<String>>();
+        let err = solve(&input).err().unwrap();
+
+        assert_eq!(6, err.line);
+        assert_eq!(0..2, err.columns);
+        assert!(err.message.starts_with("can't tell the fields apart"));
+        assert!(explain(&input).is_err());
+    }
+
+    #[test]
+    fn test_explain() {
+        let input = "class: 0-1 or 4-19
+row: 0-5 or 8-19
+seat: 0-13 or 16-19
+
+your ticket:
+11,12,13
+
+nearby tickets:
+3,9,18
+15,1,5
+5,14,9";
+
+        let input = input.split('\n').map(String::from).collect::<Vec<String>>();
+        let resolved = explain(&input)
+            .unwrap()
+            .iter()
+            .filter(|s| s.name == "resolved")
+            .map(|s| {
+                (
+                    s.get("column").unwrap().to_owned(),
+                    s.get("field").unwrap().to_owned(),
+                )
+            })
+            .collect::<Vec<(String, String)>>();
+
+        let expected = [("0", "row"), ("1", "class"), ("2", "seat")]
+            .iter()
+            .map(|(c, f)| (c.to_string(), f.to_string()))
+            .collect::<Vec<(String, String)>>();
+        assert_eq!(expected, resolved);
+    }
+}
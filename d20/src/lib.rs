@@ -0,0 +1,10 @@
+//! Day 20: Jurassic Jigsaw
+
+use std::fmt::Display;
+
+use aoc_2020::Diagnostic;
+
+/// Solves both parts of the puzzle. Not implemented yet.
+pub fn solve(_input: &[String]) -> Result<(impl Display, impl Display), Diagnostic> {
+    Ok((0, 0))
+}
@@ -1,18 +1,14 @@
-use std::fmt::Display;
 use std::time::Instant;
 
-use aoc_2020::get_input;
-
-fn solve(input: &[String]) -> (impl Display, impl Display) {
-    (0, 0)
-}
+use aoc_2020::{exit_with_diagnostic, get_input};
+use d21::solve;
 
 fn main() {
     let input = get_input("d21.txt");
 
     let start = Instant::now();
 
-    let (r1, r2) = solve(input.as_slice());
+    let (r1, r2) = solve(input.as_slice()).unwrap_or_else(|e| exit_with_diagnostic(e, "d21.txt"));
 
     let t = start.elapsed().as_micros() as f64 / 1000.0;
 
@@ -2,20 +2,33 @@
 
 set -eu
 
-EVENT=2020
-
 . ~/usr/lib/log.sh
 
 if [ $# -lt 1 ]; then
     fatal "Usage: $0 day"
 fi
 
+config() {
+    cargo run --quiet --package aoc -- config "$1"
+}
+
+EVENT="$(config year)"
+INPUT_DIR="$(config input_dir)"
+SESSION_FILE="$(config session_file)"
+TIMEOUT="$(config timeouts.http)"
+
 day="$(printf "%d" "${1}")"
 name="$(printf "d%02d" "${1}")"
 
 [ "${day}" -lt 1 ] && fatal "Invalid day: ${1}"
 
-[ -d "input" ] || mkdir input
+if [ -z "${SESSION:-}" ]; then
+    [ -f "${SESSION_FILE}" ] || fatal "No session: set SESSION or create ${SESSION_FILE}"
+    SESSION="$(cat "${SESSION_FILE}")"
+fi
+
+[ -d "${INPUT_DIR}" ] || mkdir "${INPUT_DIR}"
 
-curl --fail --cookie "session=${SESSION}" "https://adventofcode.com/${EVENT}/day/${day}/input" > "input/${name}.txt"
-git add "input/${name}.txt"
+curl --fail --max-time "${TIMEOUT}" --cookie "session=${SESSION}" "https://adventofcode.com/${EVENT}/day/${day}/input" > "${INPUT_DIR}/${name}.txt"
+cargo run --quiet --bin aoc-crypt -- encrypt "${INPUT_DIR}/${name}.txt"
+git add "${INPUT_DIR}/${name}.txt.enc"
@@ -0,0 +1,317 @@
+//! Assignment of items to distinct slots, given the candidate slots of each item, e.g. of ticket
+//! columns to fields.
+//!
+//! Items whose candidates are down to a single slot are assigned first, which removes that slot
+//! from the other items, and the items left over are assigned by maximum bipartite matching.
+
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt;
+
+use crate::bitset::BitSet;
+
+/// Error returned when the items can't be assigned to a single set of slots.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AssignError {
+    /// Not every item can get its own slot, e.g. when two items only fit the same slot. The items
+    /// left without a slot by a maximum matching.
+    Impossible(Vec<usize>),
+    /// Several assignments work. The items that don't get the same slot in all of them.
+    Ambiguous(Vec<usize>),
+}
+
+impl fmt::Display for AssignError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AssignError::Impossible(items) => write!(f, "no slot left for items {:?}", items),
+            AssignError::Ambiguous(items) => write!(f, "several slots fit items {:?}", items),
+        }
+    }
+}
+
+impl Error for AssignError {}
+
+/// How the items were assigned to their slots.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Assignment {
+    /// The slot of each item.
+    pub slots: Vec<usize>,
+    /// The items assigned by elimination, in the order `propagate` assigned them. The other
+    /// items were assigned by matching.
+    pub eliminated: Vec<usize>,
+}
+
+/// Assigns the items with a single candidate slot and removes their slot from the candidates
+/// of the other items, until none is left. Returns these items in the order they were
+/// assigned, the lowest one first when several are.
+///
+/// Items may be left without candidates, when two of them had the same single slot.
+pub fn propagate(candidates: &mut [BitSet]) -> Vec<usize> {
+    let mut assigned = vec![false; candidates.len()];
+    let mut order = Vec::new();
+
+    loop {
+        let single = (0..candidates.len()).find(|&i| !assigned[i] && candidates[i].len() == 1);
+        let item = match single {
+            Some(item) => item,
+            None => return order,
+        };
+        assigned[item] = true;
+        order.push(item);
+
+        let slot = candidates[item].iter().next().unwrap();
+        for (i, c) in candidates.iter_mut().enumerate() {
+            if i != item {
+                c.remove(slot);
+            }
+        }
+    }
+}
+
+/// Extends the matching with an augmenting path from `item` along the BFS layers of `dist`.
+fn augment(
+    item: usize,
+    adjacency: &[Vec<usize>],
+    dist: &mut [usize],
+    slot_of: &mut [Option<usize>],
+    item_of: &mut [Option<usize>],
+) -> bool {
+    for &slot in &adjacency[item] {
+        let found = match item_of[slot] {
+            None => true,
+            Some(next) => {
+                dist[next] == dist[item] + 1 && augment(next, adjacency, dist, slot_of, item_of)
+            }
+        };
+
+        if found {
+            slot_of[item] = Some(slot);
+            item_of[slot] = Some(item);
+            return true;
+        }
+    }
+
+    // Dead end, for the rest of this phase.
+    dist[item] = usize::MAX;
+
+    false
+}
+
+/// A maximum matching of the items to their candidate slots, by Hopcroft-Karp: the slot of each
+/// item, if it has one.
+pub fn max_matching(candidates: &[BitSet]) -> Vec<Option<usize>> {
+    let adjacency = candidates
+        .iter()
+        .map(|c| c.iter().collect())
+        .collect::<Vec<Vec<usize>>>();
+    let slots = adjacency.iter().flatten().max().map_or(0, |&s| s + 1);
+
+    let mut slot_of = vec![None; candidates.len()];
+    let mut item_of = vec![None; slots];
+    let mut dist = vec![usize::MAX; candidates.len()];
+
+    loop {
+        // Layers of the alternating paths from the free items.
+        let mut queue = VecDeque::new();
+        for (item, slot) in slot_of.iter().enumerate() {
+            dist[item] = if slot.is_none() {
+                queue.push_back(item);
+                0
+            } else {
+                usize::MAX
+            };
+        }
+
+        let mut augmentable = false;
+        while let Some(item) = queue.pop_front() {
+            for &slot in &adjacency[item] {
+                match item_of[slot] {
+                    None => augmentable = true,
+                    Some(next) if dist[next] == usize::MAX => {
+                        dist[next] = dist[item] + 1;
+                        queue.push_back(next);
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+        if !augmentable {
+            return slot_of;
+        }
+
+        for item in 0..candidates.len() {
+            if slot_of[item].is_none() && dist[item] == 0 {
+                augment(item, &adjacency, &mut dist, &mut slot_of, &mut item_of);
+            }
+        }
+    }
+}
+
+/// Whether `item` can take another slot in a complete assignment: whether an alternating path
+/// from it ends on a free slot or on its own slot.
+fn has_alternative(
+    candidates: &[BitSet],
+    slot_of: &[usize],
+    item_of: &[Option<usize>],
+    item: usize,
+) -> bool {
+    let mut seen = vec![false; candidates.len()];
+    let mut stack = vec![item];
+    seen[item] = true;
+
+    while let Some(i) = stack.pop() {
+        for slot in candidates[i].iter().filter(|&s| s != slot_of[i]) {
+            match item_of.get(slot).copied().flatten() {
+                None => return true,
+                Some(next) if next == item => return true,
+                Some(next) if !seen[next] => {
+                    seen[next] = true;
+                    stack.push(next);
+                }
+                Some(_) => {}
+            }
+        }
+    }
+
+    false
+}
+
+/// The slot of each item, given the candidate slots of each item, if there is a single way to
+/// give every item its own slot.
+pub fn assign(candidates: &[BitSet]) -> Result<Assignment, AssignError> {
+    let mut candidates = candidates.to_vec();
+    let eliminated = propagate(&mut candidates);
+
+    let matching = max_matching(&candidates);
+    let unmatched = (0..matching.len())
+        .filter(|&i| matching[i].is_none())
+        .collect::<Vec<usize>>();
+    if !unmatched.is_empty() {
+        return Err(AssignError::Impossible(unmatched));
+    }
+
+    let slot_of = matching.into_iter().flatten().collect::<Vec<usize>>();
+    let mut item_of = vec![None; slot_of.iter().max().map_or(0, |&s| s + 1)];
+    for (item, &slot) in slot_of.iter().enumerate() {
+        item_of[slot] = Some(item);
+    }
+
+    let ambiguous = (0..slot_of.len())
+        .filter(|&i| has_alternative(&candidates, &slot_of, &item_of, i))
+        .collect::<Vec<usize>>();
+    if !ambiguous.is_empty() {
+        return Err(AssignError::Ambiguous(ambiguous));
+    }
+
+    Ok(Assignment {
+        slots: slot_of,
+        eliminated,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::assign::{assign, max_matching, propagate, AssignError, Assignment};
+    use crate::bitset::BitSet;
+
+    fn relation(sets: &[&[usize]]) -> Vec<BitSet> {
+        sets.iter().map(|s| s.iter().copied().collect()).collect()
+    }
+
+    /// Every assignment of distinct slots below `slots` to `items` items.
+    fn assignments(items: usize, slots: usize) -> Vec<Vec<usize>> {
+        (0..items).fold(vec![vec![]], |partial, _| {
+            partial
+                .into_iter()
+                .flat_map(|a| {
+                    (0..slots)
+                        .filter(|s| !a.contains(s))
+                        .map(|s| {
+                            let mut a = a.clone();
+                            a.push(s);
+                            a
+                        })
+                        .collect::<Vec<Vec<usize>>>()
+                })
+                .collect()
+        })
+    }
+
+    #[test]
+    fn test_propagate() {
+        let mut candidates = relation(&[&[0, 1, 2], &[1], &[0, 1]]);
+
+        let original = candidates.clone();
+        assert_eq!(vec![1, 2, 0], propagate(&mut candidates));
+        assert_eq!(relation(&[&[2], &[1], &[0]]), candidates);
+        assert_eq!(
+            Ok(Assignment {
+                slots: vec![2, 1, 0],
+                eliminated: vec![1, 2, 0],
+            }),
+            assign(&original)
+        );
+
+        let mut conflict = relation(&[&[3], &[3], &[1, 3]]);
+        assert_eq!(vec![0, 2], propagate(&mut conflict));
+        assert!(conflict[1].is_empty());
+    }
+
+    #[test]
+    fn test_assign() {
+        // No item has a single candidate, but the last one must take 3 for the others to get a
+        // slot, which they can take in two ways.
+        let candidates = relation(&[&[0, 1], &[1, 2], &[2, 0], &[0, 3]]);
+        assert_eq!(
+            Err(AssignError::Ambiguous(vec![0, 1, 2])),
+            assign(&candidates)
+        );
+
+        let candidates = relation(&[&[0, 1], &[0, 1], &[2]]);
+        assert_eq!(Err(AssignError::Ambiguous(vec![0, 1])), assign(&candidates));
+
+        let candidates = relation(&[&[0, 1], &[0], &[1], &[1, 2]]);
+        assert!(matches!(
+            assign(&candidates),
+            Err(AssignError::Impossible(_))
+        ));
+        assert_eq!(3, max_matching(&candidates).iter().flatten().count());
+
+        assert_eq!(Ok(vec![]), assign(&[]).map(|a| a.slots));
+    }
+
+    #[test]
+    fn test_against_brute_force() {
+        let (items, slots) = (3, 4);
+
+        for bits in 0..1u32 << (items * slots) {
+            let candidates = (0..items)
+                .map(|i| {
+                    (0..slots)
+                        .filter(|s| bits & 1 << (i * slots + s) != 0)
+                        .collect()
+                })
+                .collect::<Vec<BitSet>>();
+            let solutions = assignments(items, slots)
+                .into_iter()
+                .filter(|a| {
+                    a.iter()
+                        .enumerate()
+                        .all(|(i, &s)| candidates[i].contains(s))
+                })
+                .collect::<Vec<Vec<usize>>>();
+
+            let res = assign(&candidates);
+            match solutions.len() {
+                0 => assert!(matches!(res, Err(AssignError::Impossible(_))), "{:b}", bits),
+                1 => assert_eq!(Ok(solutions[0].clone()), res.map(|a| a.slots), "{:b}", bits),
+                _ => {
+                    let ambiguous = (0..items)
+                        .filter(|&i| solutions.iter().any(|a| a[i] != solutions[0][i]))
+                        .collect();
+                    assert_eq!(Err(AssignError::Ambiguous(ambiguous)), res, "{:b}", bits);
+                }
+            }
+        }
+    }
+}
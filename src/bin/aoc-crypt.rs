@@ -0,0 +1,97 @@
+use std::env;
+use std::ffi::OsStr;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+
+use aoc_2020::config;
+use aoc_2020::crypt::{self, Key, ENCRYPTED_EXT};
+
+const USAGE: &str = "Usage: aoc-crypt <encrypt|decrypt> [FILE...]
+
+  encrypt  Encrypt (or re-encrypt) input/*.txt and input/*/*.txt into .txt.enc files
+  decrypt  Decrypt input/*.txt.enc and input/*/*.txt.enc back into .txt files
+
+The key is read from AOC_INPUT_KEY, the file named by AOC_INPUT_KEY_FILE or .input-key.
+The input directory is the input_dir of aoc.toml.";
+
+fn list_inputs(ext: &str) -> Vec<PathBuf> {
+    let dir = &config::get().input_dir;
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => fatal(&format!("Unable to list {}/: {}", dir, e)),
+    };
+
+    // Named inputs live one level down, in `dNN/`.
+    let paths = entries.map(|e| e.unwrap().path()).flat_map(|p| {
+        if p.is_dir() {
+            fs::read_dir(&p)
+                .map(|entries| entries.map(|e| e.unwrap().path()).collect())
+                .unwrap_or_default()
+        } else {
+            vec![p]
+        }
+    });
+
+    let mut files = paths
+        .filter(|p| p.is_file() && p.extension() == Some(OsStr::new(ext)))
+        .collect::<Vec<PathBuf>>();
+    files.sort();
+
+    files
+}
+
+fn encrypt_file(key: &Key, path: &Path) {
+    let plaintext = fs::read(path)
+        .unwrap_or_else(|e| fatal(&format!("Unable to read {}: {}", path.display(), e)));
+
+    let mut dest = path.as_os_str().to_owned();
+    dest.push(".");
+    dest.push(ENCRYPTED_EXT);
+
+    fs::write(&dest, crypt::encrypt(key, &plaintext))
+        .unwrap_or_else(|e| fatal(&format!("Unable to write {:?}: {}", dest, e)));
+
+    println!("{} -> {}", path.display(), Path::new(&dest).display());
+}
+
+fn decrypt_file(key: &Key, path: &Path) {
+    let data = fs::read(path)
+        .unwrap_or_else(|e| fatal(&format!("Unable to read {}: {}", path.display(), e)));
+    let plaintext = crypt::decrypt(key, &data)
+        .unwrap_or_else(|e| fatal(&format!("Unable to decrypt {}: {}", path.display(), e)));
+
+    let dest = path.with_extension("");
+
+    fs::write(&dest, plaintext)
+        .unwrap_or_else(|e| fatal(&format!("Unable to write {}: {}", dest.display(), e)));
+
+    println!("{} -> {}", path.display(), dest.display());
+}
+
+fn fatal(msg: &str) -> ! {
+    eprintln!("{}", msg);
+    process::exit(1);
+}
+
+fn main() {
+    let args = env::args().skip(1).collect::<Vec<String>>();
+
+    let (ext, action): (&str, fn(&Key, &Path)) = match args.first().map(String::as_str) {
+        Some("encrypt") => ("txt", encrypt_file),
+        Some("decrypt") => (ENCRYPTED_EXT, decrypt_file),
+        _ => fatal(USAGE),
+    };
+
+    let key = Key::load().unwrap_or_else(|e| fatal(&format!("Error: {}", e)));
+
+    let files = if args.len() > 1 {
+        args[1..].iter().map(PathBuf::from).collect()
+    } else {
+        list_inputs(ext)
+    };
+
+    for f in files {
+        action(&key, &f);
+    }
+}
@@ -0,0 +1,420 @@
+//! Sets of small integers stored as bits: `SmallBitSet` for up to 64 elements in a single
+//! word, `BitSet` for any number of them.
+
+use std::fmt;
+use std::iter::FromIterator;
+use std::ops::{BitAnd, BitOr, BitXor, Sub};
+
+/// The index of the letter `c` of `a..=z`.
+pub fn letter_index(c: char) -> Option<usize> {
+    if c.is_ascii_lowercase() {
+        Some(c as usize - 'a' as usize)
+    } else {
+        None
+    }
+}
+
+/// The letter of `a..=z` at `idx`.
+pub fn index_letter(idx: usize) -> Option<char> {
+    if idx < 26 {
+        Some((b'a' + idx as u8) as char)
+    } else {
+        None
+    }
+}
+
+/// A set of integers in `0..64`.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SmallBitSet(u64);
+
+impl SmallBitSet {
+    pub const CAPACITY: usize = 64;
+
+    pub const fn new() -> Self {
+        SmallBitSet(0)
+    }
+
+    /// The set of the bits set in `bits`.
+    pub const fn from_bits(bits: u64) -> Self {
+        SmallBitSet(bits)
+    }
+
+    /// The set of `0..width`.
+    pub fn full(width: usize) -> Self {
+        assert!(width <= Self::CAPACITY, "{} is over 64 bits", width);
+
+        SmallBitSet(u64::MAX.checked_shr(64 - width as u32).unwrap_or(0))
+    }
+
+    /// The set of the letters of `s`, `None` if it has chars out of `a..=z`.
+    pub fn from_letters(s: &str) -> Option<Self> {
+        s.chars().map(letter_index).collect()
+    }
+
+    pub const fn bits(self) -> u64 {
+        self.0
+    }
+
+    /// Adds `idx`, returning whether it wasn't there.
+    pub fn insert(&mut self, idx: usize) -> bool {
+        assert!(idx < Self::CAPACITY, "{} is over 64 bits", idx);
+        let absent = !self.contains(idx);
+        self.0 |= 1 << idx;
+
+        absent
+    }
+
+    /// Removes `idx`, returning whether it was there.
+    pub fn remove(&mut self, idx: usize) -> bool {
+        let present = self.contains(idx);
+        if present {
+            self.0 &= !(1 << idx);
+        }
+
+        present
+    }
+
+    pub fn contains(self, idx: usize) -> bool {
+        idx < Self::CAPACITY && self.0 & (1 << idx) != 0
+    }
+
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn is_subset(self, other: Self) -> bool {
+        self.0 & !other.0 == 0
+    }
+
+    /// The elements of `0..width` that aren't in the set.
+    pub fn complement(self, width: usize) -> Self {
+        SmallBitSet(!self.0) & Self::full(width)
+    }
+
+    /// The elements, in increasing order.
+    pub fn iter(self) -> impl Iterator<Item = usize> {
+        let mut bits = self.0;
+
+        std::iter::from_fn(move || {
+            if bits == 0 {
+                return None;
+            }
+            let idx = bits.trailing_zeros() as usize;
+            bits &= bits - 1;
+
+            Some(idx)
+        })
+    }
+
+    /// The letters of `a..=z` in the set, in order.
+    pub fn letters(self) -> impl Iterator<Item = char> {
+        self.iter().filter_map(index_letter)
+    }
+
+    /// Every subset, from the empty set to the whole set, in increasing order of their bits.
+    pub fn subsets(self) -> impl Iterator<Item = Self> {
+        let mask = self.0;
+        let mut next = Some(0u64);
+
+        std::iter::from_fn(move || {
+            let sub = next?;
+            next = if sub == mask {
+                None
+            } else {
+                Some(sub.wrapping_sub(mask) & mask)
+            };
+
+            Some(SmallBitSet(sub))
+        })
+    }
+}
+
+impl BitOr for SmallBitSet {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        SmallBitSet(self.0 | other.0)
+    }
+}
+
+impl BitAnd for SmallBitSet {
+    type Output = Self;
+
+    fn bitand(self, other: Self) -> Self {
+        SmallBitSet(self.0 & other.0)
+    }
+}
+
+impl BitXor for SmallBitSet {
+    type Output = Self;
+
+    fn bitxor(self, other: Self) -> Self {
+        SmallBitSet(self.0 ^ other.0)
+    }
+}
+
+impl Sub for SmallBitSet {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        SmallBitSet(self.0 & !other.0)
+    }
+}
+
+impl FromIterator<usize> for SmallBitSet {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut set = SmallBitSet::new();
+        for idx in iter {
+            set.insert(idx);
+        }
+
+        set
+    }
+}
+
+impl fmt::Display for SmallBitSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+/// A set of integers that grows to hold the largest of them.
+#[derive(Debug, Default, Clone, Eq)]
+pub struct BitSet {
+    words: Vec<u64>,
+}
+
+impl PartialEq for BitSet {
+    /// Trailing empty words don't matter.
+    fn eq(&self, other: &Self) -> bool {
+        let (short, long) = if self.words.len() <= other.words.len() {
+            (&self.words, &other.words)
+        } else {
+            (&other.words, &self.words)
+        };
+
+        long[..short.len()] == short[..] && long[short.len()..].iter().all(|&w| w == 0)
+    }
+}
+
+impl BitSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// An empty set that holds `0..capacity` without growing.
+    pub fn with_capacity(capacity: usize) -> Self {
+        BitSet {
+            words: vec![0; capacity.div_ceil(64)],
+        }
+    }
+
+    /// Adds `idx`, returning whether it wasn't there.
+    pub fn insert(&mut self, idx: usize) -> bool {
+        let (word, bit) = (idx / 64, idx % 64);
+        if word >= self.words.len() {
+            self.words.resize(word + 1, 0);
+        }
+        let absent = self.words[word] & (1 << bit) == 0;
+        self.words[word] |= 1 << bit;
+
+        absent
+    }
+
+    /// Removes `idx`, returning whether it was there.
+    pub fn remove(&mut self, idx: usize) -> bool {
+        let present = self.contains(idx);
+        if present {
+            self.words[idx / 64] &= !(1 << (idx % 64));
+        }
+
+        present
+    }
+
+    pub fn contains(&self, idx: usize) -> bool {
+        self.words
+            .get(idx / 64)
+            .is_some_and(|w| w & (1 << (idx % 64)) != 0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    pub fn clear(&mut self) {
+        self.words.iter_mut().for_each(|w| *w = 0);
+    }
+
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.words
+            .iter()
+            .enumerate()
+            .all(|(i, w)| w & !other.words.get(i).copied().unwrap_or(0) == 0)
+    }
+
+    pub fn union_with(&mut self, other: &Self) {
+        if other.words.len() > self.words.len() {
+            self.words.resize(other.words.len(), 0);
+        }
+        for (w, o) in self.words.iter_mut().zip(&other.words) {
+            *w |= o;
+        }
+    }
+
+    pub fn intersect_with(&mut self, other: &Self) {
+        for (i, w) in self.words.iter_mut().enumerate() {
+            *w &= other.words.get(i).copied().unwrap_or(0);
+        }
+    }
+
+    pub fn difference_with(&mut self, other: &Self) {
+        for (w, o) in self.words.iter_mut().zip(&other.words) {
+            *w &= !o;
+        }
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut set = self.clone();
+        set.union_with(other);
+        set
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut set = self.clone();
+        set.intersect_with(other);
+        set
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut set = self.clone();
+        set.difference_with(other);
+        set
+    }
+
+    /// The elements, in increasing order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, &w)| {
+            SmallBitSet::from_bits(w)
+                .iter()
+                .map(move |bit| i * 64 + bit)
+        })
+    }
+}
+
+impl FromIterator<usize> for BitSet {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut set = BitSet::new();
+        for idx in iter {
+            set.insert(idx);
+        }
+
+        set
+    }
+}
+
+impl From<SmallBitSet> for BitSet {
+    fn from(set: SmallBitSet) -> Self {
+        BitSet {
+            words: vec![set.bits()],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use crate::bitset::{BitSet, SmallBitSet};
+
+    #[test]
+    fn test_small_bit_set() {
+        let a = SmallBitSet::from_letters("abcx").unwrap();
+        let b = SmallBitSet::from_letters("bcd").unwrap();
+
+        assert_eq!(4, a.len());
+        assert_eq!("bc", (a & b).letters().collect::<String>());
+        assert_eq!("abcdx", (a | b).letters().collect::<String>());
+        assert_eq!("ax", (a - b).letters().collect::<String>());
+        assert_eq!("adx", (a ^ b).letters().collect::<String>());
+        assert!((a & b).is_subset(b));
+        assert!(!a.is_subset(b));
+        assert_eq!(None, SmallBitSet::from_letters("aB"));
+
+        assert_eq!(0b1111, SmallBitSet::full(4).bits());
+        assert_eq!(u64::MAX, SmallBitSet::full(64).bits());
+        assert!(SmallBitSet::full(0).is_empty());
+        assert_eq!(0b1010, SmallBitSet::from_bits(0b0101).complement(4).bits());
+        assert_eq!(
+            vec![0, 3, 63],
+            SmallBitSet::from_bits(1 | 8 | 1 << 63)
+                .iter()
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            "{1, 4}",
+            vec![4, 1].into_iter().collect::<SmallBitSet>().to_string()
+        );
+    }
+
+    #[test]
+    fn test_subsets() {
+        let set = SmallBitSet::from_bits(0b1011_0010);
+        let subsets = set.subsets().collect::<Vec<SmallBitSet>>();
+
+        assert_eq!(1 << set.len(), subsets.len());
+        assert!(subsets.iter().all(|s| s.is_subset(set)));
+        assert!(subsets.windows(2).all(|w| w[0].bits() < w[1].bits()));
+        assert_eq!(1, SmallBitSet::new().subsets().count());
+        assert_eq!(
+            vec![0b000, 0b001, 0b100, 0b101],
+            SmallBitSet::from_bits(0b101)
+                .subsets()
+                .map(|s| s.bits())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_bit_set() {
+        let values = [3, 64, 65, 200, 1000];
+        let mut set = values.iter().copied().collect::<BitSet>();
+        let brute = values.iter().copied().collect::<BTreeSet<usize>>();
+
+        assert_eq!(
+            brute.iter().copied().collect::<Vec<_>>(),
+            set.iter().collect::<Vec<_>>()
+        );
+        assert_eq!(5, set.len());
+        assert!(set.contains(200));
+        assert!(!set.contains(201));
+        assert!(!set.contains(100_000));
+
+        assert!(set.remove(1000));
+        assert!(!set.remove(1000));
+        let other = vec![3, 64, 70].into_iter().collect::<BitSet>();
+        assert_eq!(
+            vec![3, 64],
+            set.intersection(&other).iter().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![65, 200],
+            set.difference(&other).iter().collect::<Vec<_>>()
+        );
+        assert_eq!(5, set.union(&other).len());
+        assert!(set.intersection(&other).is_subset(&other));
+
+        assert_eq!(BitSet::with_capacity(1000), BitSet::new());
+        assert_eq!(
+            BitSet::from(SmallBitSet::from_bits(0b1001)),
+            vec![0, 3].into_iter().collect()
+        );
+    }
+}
@@ -0,0 +1,366 @@
+//! Project settings, read from `aoc.toml` at the workspace root.
+//!
+//! Every setting has a default and can be overridden by an `AOC_*` environment variable, e.g.
+//! `AOC_INPUT_DIR` for `input_dir` or `AOC_BENCH_RUNS` for `bench.runs`.
+
+use std::env;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::str::FromStr;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// Environment variable pointing to the configuration file.
+pub const CONFIG_ENV: &str = "AOC_CONFIG";
+/// Configuration file used when `AOC_CONFIG` isn't set.
+pub const DEFAULT_FILE: &str = "aoc.toml";
+
+/// Format of the answers printed by the runner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Output {
+    Text,
+    Json,
+}
+
+impl FromStr for Output {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Output::Text),
+            "json" => Ok(Output::Json),
+            _ => Err(format!(
+                "invalid output format: {}, expected text or json",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Bench {
+    /// Number of runs of each implementation, the median is reported.
+    pub runs: usize,
+}
+
+impl Default for Bench {
+    fn default() -> Self {
+        Bench { runs: 5 }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Leaderboard {
+    /// Website serving the leaderboards, replaced by a local server in tests.
+    pub base_url: String,
+    /// Private leaderboard shown by `aoc leaderboard`, the user id of its owner.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<u64>,
+}
+
+impl Default for Leaderboard {
+    fn default() -> Self {
+        Leaderboard {
+            base_url: String::from("https://adventofcode.com"),
+            id: None,
+        }
+    }
+}
+
+/// Time limits, in seconds.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Timeouts {
+    /// Time limit of each request to `aoc serve`.
+    pub serve: f64,
+    /// Time limit of the requests to the Advent of Code website.
+    pub http: f64,
+}
+
+impl Timeouts {
+    /// The first timeout that isn't a number of seconds, e.g. a negative one, with its name.
+    fn invalid(&self) -> Option<(&'static str, f64)> {
+        [("timeouts.serve", self.serve), ("timeouts.http", self.http)]
+            .iter()
+            .copied()
+            .find(|&(_, secs)| !is_timeout(secs))
+    }
+}
+
+/// Whether `secs` is a number of seconds that a `Duration` can hold.
+fn is_timeout(secs: f64) -> bool {
+    Duration::try_from_secs_f64(secs).is_ok()
+}
+
+impl Default for Timeouts {
+    fn default() -> Self {
+        Timeouts {
+            serve: 10.0,
+            http: 30.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub year: u16,
+    /// Directory of the puzzle inputs and their golden answers.
+    pub input_dir: String,
+    /// Directory of the example inputs, used by `aoc run --example`.
+    pub example_dir: String,
+    /// File holding the session cookie of the Advent of Code website.
+    pub session_file: String,
+    pub output: Output,
+    /// Directory of the cached answers.
+    pub cache_dir: String,
+    pub bench: Bench,
+    pub leaderboard: Leaderboard,
+    pub timeouts: Timeouts,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            year: 2020,
+            input_dir: String::from("input"),
+            example_dir: String::from("examples"),
+            session_file: String::from(".session"),
+            output: Output::Text,
+            cache_dir: String::from(".aoc-cache"),
+            bench: Bench::default(),
+            leaderboard: Leaderboard::default(),
+            timeouts: Timeouts::default(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    UnreadableFile(String, io::Error),
+    Invalid(String, toml::de::Error),
+    InvalidEnv(&'static str, String),
+    InvalidTimeout(&'static str, f64),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::UnreadableFile(path, e) => write!(f, "unable to read {}: {}", path, e),
+            ConfigError::Invalid(path, e) => write!(f, "invalid {}: {}", path, e),
+            ConfigError::InvalidEnv(var, e) => write!(f, "invalid {}: {}", var, e),
+            ConfigError::InvalidTimeout(key, secs) => {
+                write!(f, "invalid {}: {} isn't a number of seconds", key, secs)
+            }
+        }
+    }
+}
+
+impl Error for ConfigError {}
+
+fn parse_env<T: FromStr>(var: &'static str, value: &str) -> Result<T, ConfigError>
+where
+    <T as FromStr>::Err: fmt::Display,
+{
+    value
+        .parse()
+        .map_err(|e: T::Err| ConfigError::InvalidEnv(var, e.to_string()))
+}
+
+fn parse_timeout(var: &'static str, value: &str) -> Result<f64, ConfigError> {
+    let secs = parse_env(var, value)?;
+    if !is_timeout(secs) {
+        return Err(ConfigError::InvalidEnv(
+            var,
+            format!("{} isn't a number of seconds", value),
+        ));
+    }
+
+    Ok(secs)
+}
+
+impl Config {
+    /// Loads the configuration from `path`, or from the file named by `AOC_CONFIG`, or from
+    /// `aoc.toml`, then applies the environment variables.
+    ///
+    /// Only `aoc.toml` may be missing, the defaults are used then. Timeouts must be finite and
+    /// not negative.
+    pub fn load(path: Option<&str>) -> Result<Self, ConfigError> {
+        let path = path.map(String::from).or_else(|| env::var(CONFIG_ENV).ok());
+        let (path, required) = match path {
+            Some(path) => (path, true),
+            None => (String::from(DEFAULT_FILE), false),
+        };
+
+        let mut config = match fs::read_to_string(&path) {
+            Ok(text) => toml::from_str(&text).map_err(|e| ConfigError::Invalid(path, e))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound && !required => Config::default(),
+            Err(e) => return Err(ConfigError::UnreadableFile(path, e)),
+        };
+        if let Some((key, secs)) = config.timeouts.invalid() {
+            return Err(ConfigError::InvalidTimeout(key, secs));
+        }
+        config.apply_env(env::vars())?;
+
+        Ok(config)
+    }
+
+    /// Overrides the settings that have an `AOC_*` variable among `vars`.
+    pub fn apply_env(
+        &mut self,
+        vars: impl IntoIterator<Item = (String, String)>,
+    ) -> Result<(), ConfigError> {
+        for (var, value) in vars {
+            match var.as_str() {
+                "AOC_YEAR" => self.year = parse_env("AOC_YEAR", &value)?,
+                "AOC_INPUT_DIR" => self.input_dir = value,
+                "AOC_EXAMPLE_DIR" => self.example_dir = value,
+                "AOC_SESSION_FILE" => self.session_file = value,
+                "AOC_OUTPUT" => self.output = parse_env("AOC_OUTPUT", &value)?,
+                "AOC_CACHE_DIR" => self.cache_dir = value,
+                "AOC_BENCH_RUNS" => self.bench.runs = parse_env("AOC_BENCH_RUNS", &value)?,
+                "AOC_LEADERBOARD_URL" => self.leaderboard.base_url = value,
+                "AOC_LEADERBOARD_ID" => {
+                    self.leaderboard.id = Some(parse_env("AOC_LEADERBOARD_ID", &value)?)
+                }
+                "AOC_SERVE_TIMEOUT" => {
+                    self.timeouts.serve = parse_timeout("AOC_SERVE_TIMEOUT", &value)?
+                }
+                "AOC_HTTP_TIMEOUT" => {
+                    self.timeouts.http = parse_timeout("AOC_HTTP_TIMEOUT", &value)?
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The value of the setting `key`, e.g. `bench.runs`, as written in `aoc.toml`.
+    pub fn get(&self, key: &str) -> Option<String> {
+        let mut value = toml::Value::try_from(self).ok()?;
+        for k in key.split('.') {
+            value = value.get(k)?.clone();
+        }
+
+        match value {
+            toml::Value::String(s) => Some(s),
+            toml::Value::Table(t) => Some(toml::to_string(&t).ok()?.trim_end().to_owned()),
+            v => Some(v.to_string()),
+        }
+    }
+}
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// Makes `config` the configuration returned by `get`, returning false if `get` already
+/// returned another one.
+pub fn init(config: Config) -> bool {
+    CONFIG.set(config).is_ok()
+}
+
+/// The configuration given to `init`, or else the one loaded from the default locations.
+///
+/// Exits when the configuration can't be loaded.
+pub fn get() -> &'static Config {
+    CONFIG.get_or_init(|| {
+        Config::load(None).unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+
+    use crate::config::{Config, Output};
+
+    fn vars(vars: &[(&str, &str)]) -> Vec<(String, String)> {
+        vars.iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse() {
+        let config: Config =
+            toml::from_str("year = 2021\ninput_dir = \"puzzles\"\n\n[bench]\nruns = 11\n").unwrap();
+
+        assert_eq!(2021, config.year);
+        assert_eq!("puzzles", config.input_dir);
+        assert_eq!(11, config.bench.runs);
+        assert_eq!(Config::default().timeouts, config.timeouts);
+
+        assert!(toml::from_str::<Config>("yeer = 2021").is_err());
+    }
+
+    #[test]
+    fn test_env() {
+        let mut config = Config::default();
+        config
+            .apply_env(vars(&[
+                ("AOC_OUTPUT", "json"),
+                ("AOC_BENCH_RUNS", "3"),
+                ("HOME", "/root"),
+            ]))
+            .unwrap();
+
+        assert_eq!(Output::Json, config.output);
+        assert_eq!(3, config.bench.runs);
+
+        let err = config
+            .apply_env(vars(&[("AOC_BENCH_RUNS", "many")]))
+            .unwrap_err();
+        assert!(
+            err.to_string().starts_with("invalid AOC_BENCH_RUNS"),
+            "{}",
+            err
+        );
+
+        for secs in &["-1", "NaN", "inf", "1e400"] {
+            let err = config
+                .apply_env(vars(&[("AOC_HTTP_TIMEOUT", secs)]))
+                .unwrap_err();
+            assert!(
+                err.to_string().starts_with("invalid AOC_HTTP_TIMEOUT"),
+                "{}",
+                err
+            );
+        }
+        assert_eq!(30.0, config.timeouts.http);
+    }
+
+    #[test]
+    fn test_invalid_timeout() {
+        let path = env::temp_dir().join(format!("aoc-timeouts-{}.toml", std::process::id()));
+        fs::write(&path, "[timeouts]\nserve = -1.0\n").unwrap();
+        let err = Config::load(path.to_str()).unwrap_err();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            "invalid timeouts.serve: -1 isn't a number of seconds",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn test_get() {
+        let config = Config::default();
+
+        assert_eq!(Some(String::from("2020")), config.get("year"));
+        assert_eq!(Some(String::from("input")), config.get("input_dir"));
+        assert_eq!(Some(String::from("10.0")), config.get("timeouts.serve"));
+        assert_eq!(Some(String::from("runs = 5")), config.get("bench"));
+        assert_eq!(None, config.get("bench.iterations"));
+    }
+}
@@ -0,0 +1,163 @@
+use std::env;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Nonce};
+use sha2::Sha256;
+
+/// Environment variable holding the input key.
+pub const KEY_ENV: &str = "AOC_INPUT_KEY";
+/// Environment variable pointing to a file holding the input key.
+pub const KEY_FILE_ENV: &str = "AOC_INPUT_KEY_FILE";
+/// Key file used when neither environment variable is set.
+pub const DEFAULT_KEY_FILE: &str = ".input-key";
+
+/// Extension appended to encrypted input files, e.g. `input/d01.txt.enc`.
+pub const ENCRYPTED_EXT: &str = "enc";
+
+const MAGIC: &[u8] = b"AOC2";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const HEADER_LEN: usize = MAGIC.len() + SALT_LEN + NONCE_LEN;
+
+/// PBKDF2-HMAC-SHA256 iterations deriving a file key from the passphrase.
+const ROUNDS: u32 = 100_000;
+
+#[derive(Debug)]
+pub enum CryptError {
+    MissingKey,
+    UnreadableKeyFile(PathBuf, io::Error),
+    Malformed,
+    Decryption,
+}
+
+impl fmt::Display for CryptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CryptError::MissingKey => write!(
+                f,
+                "no input key found: set {} or {}, or create {}",
+                KEY_ENV, KEY_FILE_ENV, DEFAULT_KEY_FILE
+            ),
+            CryptError::UnreadableKeyFile(path, e) => {
+                write!(f, "unable to read key file {}: {}", path.display(), e)
+            }
+            CryptError::Malformed => write!(f, "not an encrypted input file"),
+            CryptError::Decryption => write!(f, "decryption failed, wrong key?"),
+        }
+    }
+}
+
+impl Error for CryptError {}
+
+/// The passphrase from which the key of each file is derived, with the salt of the file.
+pub struct Key(String);
+
+impl Key {
+    pub fn from_passphrase(passphrase: &str) -> Self {
+        Key(String::from(passphrase.trim()))
+    }
+
+    /// Loads the key from `AOC_INPUT_KEY`, then from the file named by `AOC_INPUT_KEY_FILE`,
+    /// then from `.input-key`.
+    pub fn load() -> Result<Self, CryptError> {
+        if let Ok(passphrase) = env::var(KEY_ENV) {
+            if !passphrase.trim().is_empty() {
+                return Ok(Key::from_passphrase(&passphrase));
+            }
+        }
+
+        let (path, required) = match env::var_os(KEY_FILE_ENV) {
+            Some(path) => (PathBuf::from(path), true),
+            None => (PathBuf::from(DEFAULT_KEY_FILE), false),
+        };
+
+        match fs::read_to_string(&path) {
+            Ok(passphrase) if !passphrase.trim().is_empty() => {
+                Ok(Key::from_passphrase(&passphrase))
+            }
+            Ok(_) => Err(CryptError::MissingKey),
+            Err(e) if e.kind() == io::ErrorKind::NotFound && !required => {
+                Err(CryptError::MissingKey)
+            }
+            Err(e) => Err(CryptError::UnreadableKeyFile(path, e)),
+        }
+    }
+
+    fn cipher(&self, salt: &[u8]) -> ChaCha20Poly1305 {
+        let mut key = [0u8; 32];
+        pbkdf2::pbkdf2_hmac::<Sha256>(self.0.as_bytes(), salt, ROUNDS, &mut key);
+
+        ChaCha20Poly1305::new(&key.into())
+    }
+}
+
+/// Encrypts `plaintext` under a fresh salt and nonce, both stored in the header.
+pub fn encrypt(key: &Key, plaintext: &[u8]) -> Vec<u8> {
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = key
+        .cipher(&salt)
+        .encrypt(&nonce, plaintext)
+        .expect("Encryption failed");
+
+    let mut data = Vec::with_capacity(HEADER_LEN + ciphertext.len());
+    data.extend_from_slice(MAGIC);
+    data.extend_from_slice(&salt);
+    data.extend_from_slice(&nonce);
+    data.extend_from_slice(&ciphertext);
+
+    data
+}
+
+pub fn decrypt(key: &Key, data: &[u8]) -> Result<Vec<u8>, CryptError> {
+    if data.len() < HEADER_LEN || !data.starts_with(MAGIC) {
+        return Err(CryptError::Malformed);
+    }
+
+    let (salt, rest) = data[MAGIC.len()..].split_at(SALT_LEN);
+    let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+
+    key.cipher(salt)
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| CryptError::Decryption)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::crypt::{decrypt, encrypt, CryptError, Key};
+
+    #[test]
+    fn test_round_trip() {
+        let key = Key::from_passphrase("correct horse battery staple");
+        let data = encrypt(&key, b"1721\n979\n366\n");
+
+        assert_eq!(b"1721\n979\n366\n".to_vec(), decrypt(&key, &data).unwrap());
+
+        // Each file gets its own salt, hence its own key.
+        let other = encrypt(&key, b"1721\n979\n366\n");
+        assert_ne!(data[4..20], other[4..20]);
+        assert_eq!(b"1721\n979\n366\n".to_vec(), decrypt(&key, &other).unwrap());
+    }
+
+    #[test]
+    fn test_wrong_key() {
+        let data = encrypt(&Key::from_passphrase("key"), b"1721");
+        let res = decrypt(&Key::from_passphrase("other key"), &data);
+
+        assert!(matches!(res, Err(CryptError::Decryption)));
+    }
+
+    #[test]
+    fn test_malformed() {
+        let res = decrypt(&Key::from_passphrase("key"), b"1721\n979\n366\n");
+
+        assert!(matches!(res, Err(CryptError::Malformed)));
+    }
+}
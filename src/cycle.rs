@@ -0,0 +1,299 @@
+//! Detection of the cycles of sequences of states, where each state is computed from the
+//! previous one, and extrapolation of these sequences.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Where a sequence starts repeating itself: the state after `start + len` steps is the same
+/// as the one after `start` steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// Number of steps before entering the cycle.
+    pub start: usize,
+    /// Number of steps of the cycle, at least 1.
+    pub len: usize,
+}
+
+impl Cycle {
+    /// The first step, below `start + len`, at which the sequence is in the state of step `n`.
+    pub fn index(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.len
+        }
+    }
+}
+
+/// Finds the cycle of the sequence `start`, `f(start)`, `f(f(start))`, ... with Floyd's
+/// algorithm, returning it along with its first state.
+///
+/// Uses constant memory, but never returns if the sequence doesn't repeat.
+pub fn floyd<T: Clone + Eq>(start: T, mut f: impl FnMut(&T) -> T) -> (Cycle, T) {
+    let mut tortoise = f(&start);
+    let mut hare = f(&tortoise);
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        let half = f(&hare);
+        hare = f(&half);
+    }
+
+    // The hare is now a multiple of the cycle length ahead: walking from the start and from the
+    // hare at the same pace meet at the start of the cycle.
+    let mut tortoise = start;
+    let mut cycle_start = 0;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        cycle_start += 1;
+    }
+
+    let mut len = 1;
+    let mut hare = f(&tortoise);
+    while tortoise != hare {
+        hare = f(&hare);
+        len += 1;
+    }
+
+    (
+        Cycle {
+            start: cycle_start,
+            len,
+        },
+        tortoise,
+    )
+}
+
+/// Same as `floyd`, with Brent's algorithm, which computes fewer states.
+pub fn brent<T: Clone + Eq>(start: T, mut f: impl FnMut(&T) -> T) -> (Cycle, T) {
+    // The tortoise teleports to the hare at each power of two until the hare catches it.
+    let (mut power, mut len) = (1, 1);
+    let mut tortoise = start.clone();
+    let mut hare = f(&start);
+    while tortoise != hare {
+        if power == len {
+            tortoise = hare.clone();
+            power *= 2;
+            len = 0;
+        }
+        hare = f(&hare);
+        len += 1;
+    }
+
+    let mut tortoise = start;
+    let mut hare = tortoise.clone();
+    for _ in 0..len {
+        hare = f(&hare);
+    }
+    let mut cycle_start = 0;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        cycle_start += 1;
+    }
+
+    (
+        Cycle {
+            start: cycle_start,
+            len,
+        },
+        tortoise,
+    )
+}
+
+/// The state after `n` steps of the sequence `start`, `f(start)`, ..., computing at most the
+/// states up to its first repeat.
+pub fn state_at<T: Clone + Eq>(start: T, mut f: impl FnMut(&T) -> T, n: usize) -> T {
+    let (cycle, first) = brent(start.clone(), &mut f);
+    let (mut state, steps) = if n < cycle.start {
+        (start, n)
+    } else {
+        (first, cycle.index(n) - cycle.start)
+    };
+
+    for _ in 0..steps {
+        state = f(&state);
+    }
+
+    state
+}
+
+/// The states of a sequence, up to its first repeat.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct History<T> {
+    /// The distinct states, in order.
+    pub states: Vec<T>,
+    pub cycle: Cycle,
+}
+
+impl<T> History<T> {
+    /// The state after `n` steps.
+    pub fn at(&self, n: usize) -> &T {
+        &self.states[self.cycle.index(n)]
+    }
+}
+
+/// Computes the states of the sequence `start`, `f(start)`, ... until one repeats, keeping them
+/// in a hash map to find the repeat.
+pub fn history<T: Clone + Eq + Hash>(start: T, mut f: impl FnMut(&T) -> T) -> History<T> {
+    let mut seen = HashMap::new();
+    let mut states = Vec::new();
+    let mut state = start;
+
+    loop {
+        if let Some(&first) = seen.get(&state) {
+            let len = states.len() - first;
+
+            return History {
+                states,
+                cycle: Cycle { start: first, len },
+            };
+        }
+
+        let next = f(&state);
+        seen.insert(state.clone(), states.len());
+        states.push(state);
+        state = next;
+    }
+}
+
+/// Applies `f` from `start` until the state stops changing, returning the stable state and the
+/// number of steps it took to reach it.
+pub fn fixed_point<T: PartialEq>(start: T, mut f: impl FnMut(&T) -> T) -> (T, usize) {
+    let mut state = start;
+    let mut steps = 0;
+
+    loop {
+        let next = f(&state);
+        if next == state {
+            return (state, steps);
+        }
+        state = next;
+        steps += 1;
+    }
+}
+
+/// How a run of `run_until_repeat` ended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome<T> {
+    /// The step function returned `None` from `state`, reached after `steps` steps.
+    Halted { state: T, steps: usize },
+    /// `state`, reached after `cycle.start + cycle.len` steps, has the same key as the state
+    /// after `cycle.start` steps.
+    Repeated { state: T, cycle: Cycle },
+}
+
+/// Applies `step` from `start` until it returns `None` or reaches a state with the key of a
+/// previous one, which is enough to tell a loop when the rest of the state only accumulates
+/// results, e.g. a program counter.
+pub fn run_until_repeat<T, K: Eq + Hash>(
+    start: T,
+    key: impl Fn(&T) -> K,
+    mut step: impl FnMut(&T) -> Option<T>,
+) -> Outcome<T> {
+    let mut seen = HashMap::new();
+    let mut state = start;
+    let mut steps = 0;
+
+    loop {
+        if let Some(first) = seen.insert(key(&state), steps) {
+            return Outcome::Repeated {
+                state,
+                cycle: Cycle {
+                    start: first,
+                    len: steps - first,
+                },
+            };
+        }
+
+        state = match step(&state) {
+            Some(next) => next,
+            None => return Outcome::Halted { state, steps },
+        };
+        steps += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cycle::{
+        brent, fixed_point, floyd, history, run_until_repeat, state_at, Cycle, Outcome,
+    };
+
+    /// `x * x + 1 mod m`, the usual pseudo-random sequence of Pollard's rho.
+    fn rho(m: u64) -> impl Fn(&u64) -> u64 {
+        move |&x| (x * x + 1) % m
+    }
+
+    #[test]
+    fn test_against_history() {
+        for m in 1..200 {
+            for start in (0..m).step_by(7) {
+                let hist = history(start, rho(m));
+
+                assert_eq!(
+                    (hist.cycle, hist.states[hist.cycle.start]),
+                    floyd(start, rho(m))
+                );
+                assert_eq!(
+                    (hist.cycle, hist.states[hist.cycle.start]),
+                    brent(start, rho(m))
+                );
+                assert_eq!(
+                    hist.states.len(),
+                    hist.cycle.start + hist.cycle.len,
+                    "{} mod {}",
+                    start,
+                    m
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_state_at() {
+        // 3, 10, 5, 16, 8, 4, 2, 1, 4, 2, 1, ...
+        let collatz = |&x: &u64| if x % 2 == 0 { x / 2 } else { 3 * x + 1 };
+        let hist = history(3, collatz);
+
+        assert_eq!(Cycle { start: 5, len: 3 }, hist.cycle);
+        assert_eq!(&16, hist.at(3));
+        assert_eq!(&4, hist.at(1_000_000_001));
+        for n in 0..50 {
+            assert_eq!(*hist.at(n), state_at(3, collatz, n));
+        }
+        assert_eq!(2, state_at(3, collatz, usize::MAX));
+
+        assert_eq!((1, 4), fixed_point(16, |&x| (x / 2).max(1)));
+    }
+
+    #[test]
+    fn test_run_until_repeat() {
+        // Jumps forward by 2 on a ring of 5 positions, counting the jumps.
+        let jump =
+            |ring: usize| move |&(pos, jumps): &(usize, u32)| Some(((pos + 2) % ring, jumps + 1));
+
+        assert_eq!(
+            Outcome::Repeated {
+                state: (1, 5),
+                cycle: Cycle { start: 0, len: 5 },
+            },
+            run_until_repeat((1, 0), |&(pos, _)| pos, jump(5))
+        );
+
+        let halt = |&(pos, jumps): &(usize, u32)| {
+            if pos < 6 {
+                Some((pos + 2, jumps + 1))
+            } else {
+                None
+            }
+        };
+        assert_eq!(
+            Outcome::Halted {
+                state: (7, 3),
+                steps: 3,
+            },
+            run_until_repeat((1, 0), |&(pos, _)| pos, halt)
+        );
+    }
+}
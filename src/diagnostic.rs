@@ -0,0 +1,140 @@
+use std::error::Error;
+use std::fmt;
+use std::ops::Range;
+
+/// An error located in a puzzle input, rendered like a rustc diagnostic:
+///
+/// ```text
+/// error: invalid action 'X'
+///  --> input/d12.txt:3:1
+///   |
+/// 3 | X10
+///   | ^
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub file: String,
+    /// One-based line number.
+    pub line: usize,
+    /// Zero-based byte range of the offending token within `source`.
+    pub columns: Range<usize>,
+    pub message: String,
+    /// The full text of the offending line.
+    pub source: String,
+}
+
+impl Diagnostic {
+    /// Creates a diagnostic for the line at index `idx` (zero-based) of the input.
+    pub fn new(
+        idx: usize,
+        source: &str,
+        columns: Range<usize>,
+        message: impl Into<String>,
+    ) -> Self {
+        Diagnostic {
+            file: String::from("<input>"),
+            line: idx + 1,
+            columns,
+            message: message.into(),
+            source: String::from(source),
+        }
+    }
+
+    /// Creates a diagnostic pointing at `token`, which must be a slice of `source`.
+    pub fn at_token(idx: usize, source: &str, token: &str, message: impl Into<String>) -> Self {
+        let (src, tok) = (source.as_ptr() as usize, token.as_ptr() as usize);
+        assert!(
+            tok >= src && tok + token.len() <= src + source.len(),
+            "Token is not part of the source line"
+        );
+        let start = tok - src;
+
+        Diagnostic::new(idx, source, start..start + token.len(), message)
+    }
+
+    /// Creates a diagnostic spanning the whole line.
+    pub fn at_line(idx: usize, source: &str, message: impl Into<String>) -> Self {
+        Diagnostic::new(idx, source, 0..source.len(), message)
+    }
+
+    pub fn with_file(mut self, file: &str) -> Self {
+        self.file = String::from(file);
+        self
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let start = self.columns.start.min(self.source.len());
+        let end = self.columns.end.clamp(start, self.source.len());
+
+        let offset = self.source[..start].chars().count();
+        let width = self.source[start..end].chars().count().max(1);
+
+        let line_no = self.line.to_string();
+        let gutter = " ".repeat(line_no.len());
+
+        writeln!(f, "error: {}", self.message)?;
+        writeln!(
+            f,
+            "{}--> {}:{}:{}",
+            gutter,
+            self.file,
+            self.line,
+            offset + 1
+        )?;
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", line_no, self.source)?;
+        write!(
+            f,
+            "{} | {}{}",
+            gutter,
+            " ".repeat(offset),
+            "^".repeat(width)
+        )
+    }
+}
+
+impl Error for Diagnostic {}
+
+#[cfg(test)]
+mod tests {
+    use crate::Diagnostic;
+
+    #[test]
+    fn test_render() {
+        let line = "mem[8] = 11x";
+        let diag =
+            Diagnostic::at_token(2, line, &line[9..], "invalid value").with_file("input/d14.txt");
+
+        let expected = "error: invalid value
+ --> input/d14.txt:3:10
+  |
+3 | mem[8] = 11x
+  |          ^^^";
+
+        assert_eq!(expected, diag.to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "Token is not part of the source line")]
+    fn test_token_outside_source() {
+        let input = "mask = X1\nmem[8] = 11";
+        let (mask, mem) = input.split_at(10);
+
+        Diagnostic::at_token(1, mem, &mask[7..], "invalid value");
+    }
+
+    #[test]
+    fn test_render_empty_span() {
+        let diag = Diagnostic::new(11, "F", 1..1, "missing value");
+
+        let expected = "error: missing value
+  --> <input>:12:2
+   |
+12 | F
+   |  ^";
+
+        assert_eq!(expected, diag.to_string());
+    }
+}
@@ -0,0 +1,80 @@
+//! Structured explanations of the intermediate steps of a solution.
+
+use std::fmt::{self, Display};
+
+/// An intermediate step, e.g. `[part 2] sync bus=13 period=91 phase=77`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub part: u8,
+    pub name: &'static str,
+    pub values: Vec<(&'static str, String)>,
+}
+
+impl Step {
+    pub fn new(part: u8, name: &'static str) -> Self {
+        Step {
+            part,
+            name,
+            values: Vec::new(),
+        }
+    }
+
+    pub fn with(mut self, key: &'static str, value: impl Display) -> Self {
+        self.values.push((key, value.to_string()));
+        self
+    }
+
+    /// The value of `key`, if the step has one.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.values
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+impl Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[part {}] {}", self.part, self.name)?;
+        for (k, v) in &self.values {
+            write!(f, " {}={}", k, v)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Receives the steps of a solution. Steps are built lazily so that `NoTrace` costs nothing.
+pub trait Trace {
+    fn step(&mut self, step: impl FnOnce() -> Step);
+}
+
+/// Discards the steps.
+pub struct NoTrace;
+
+impl Trace for NoTrace {
+    fn step(&mut self, _step: impl FnOnce() -> Step) {}
+}
+
+impl Trace for Vec<Step> {
+    fn step(&mut self, step: impl FnOnce() -> Step) {
+        self.push(step());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::explain::{NoTrace, Step, Trace};
+
+    #[test]
+    fn test_trace() {
+        let mut steps = Vec::new();
+        steps.step(|| Step::new(2, "sync").with("bus", 13).with("period", 91));
+        NoTrace.step(|| panic!("NoTrace shouldn't build steps"));
+
+        assert_eq!(1, steps.len());
+        assert_eq!("[part 2] sync bus=13 period=91", steps[0].to_string());
+        assert_eq!(Some("91"), steps[0].get("period"));
+        assert_eq!(None, steps[0].get("phase"));
+    }
+}
@@ -0,0 +1,72 @@
+//! Helpers shared by the per-day random input generators.
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+pub use rand::seq::SliceRandom;
+pub use rand::Rng;
+
+/// Seeded random number generator, producing the same inputs for a given seed on every platform.
+pub type GenRng = ChaCha8Rng;
+
+pub fn rng(seed: u64) -> GenRng {
+    GenRng::seed_from_u64(seed)
+}
+
+/// A generated puzzle input, with the answers planted in it when they are known.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Generated {
+    pub input: Vec<String>,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl Generated {
+    pub fn new(input: Vec<String>) -> Self {
+        Generated {
+            input,
+            part1: None,
+            part2: None,
+        }
+    }
+
+    pub fn with_answers(input: Vec<String>, part1: impl ToString, part2: impl ToString) -> Self {
+        Generated {
+            input,
+            part1: Some(part1.to_string()),
+            part2: Some(part2.to_string()),
+        }
+    }
+}
+
+/// Signature of a day's generator: `size` scales the input, `seed` makes it reproducible.
+pub type Generator = fn(size: usize, seed: u64) -> Generated;
+
+const CONSONANTS: &[u8] = b"bcdfghjklmnprstvz";
+const VOWELS: &[u8] = b"aeiou";
+
+/// A pronounceable lowercase word made of `syllables` consonant-vowel pairs.
+pub fn word<R: Rng>(rng: &mut R, syllables: usize) -> String {
+    (0..syllables)
+        .flat_map(|_| {
+            [
+                *CONSONANTS.choose(rng).unwrap() as char,
+                *VOWELS.choose(rng).unwrap() as char,
+            ]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::gen::{rng, word};
+
+    #[test]
+    fn test_word() {
+        let w = word(&mut rng(0), 3);
+
+        assert_eq!(6, w.len());
+        assert!(w.chars().all(|c| c.is_ascii_lowercase()));
+        assert_eq!(w, word(&mut rng(0), 3));
+    }
+}
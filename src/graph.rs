@@ -0,0 +1,390 @@
+//! Directed graphs with weighted edges, whose nodes are interned to `NodeId`s.
+
+use std::borrow::Borrow;
+use std::collections::{HashMap, VecDeque};
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::hash::Hash;
+
+/// Index of a node in its graph, in order of insertion.
+pub type NodeId = usize;
+
+/// Error returned when a graph that must be acyclic has a cycle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle {
+    /// The nodes of the cycle, each one having an edge to the next and the last one to the
+    /// first.
+    pub nodes: Vec<NodeId>,
+}
+
+impl Display for Cycle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "cycle through nodes {:?}", self.nodes)
+    }
+}
+
+impl Error for Cycle {}
+
+#[derive(Debug, Clone)]
+pub struct Graph<N, W = ()> {
+    nodes: Vec<N>,
+    ids: HashMap<N, NodeId>,
+    edges: Vec<Vec<(NodeId, W)>>,
+    reverse: Vec<Vec<(NodeId, W)>>,
+}
+
+impl<N, W> Default for Graph<N, W> {
+    fn default() -> Self {
+        Graph {
+            nodes: Vec::new(),
+            ids: HashMap::new(),
+            edges: Vec::new(),
+            reverse: Vec::new(),
+        }
+    }
+}
+
+impl<N: Eq + Hash + Clone, W: Clone> Graph<N, W> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The id of `node`, adding it to the graph if needed.
+    pub fn node(&mut self, node: N) -> NodeId {
+        if let Some(&id) = self.ids.get(&node) {
+            return id;
+        }
+
+        let id = self.nodes.len();
+        self.ids.insert(node.clone(), id);
+        self.nodes.push(node);
+        self.edges.push(Vec::new());
+        self.reverse.push(Vec::new());
+
+        id
+    }
+
+    /// Adds an edge from `from` to `to`, adding the nodes if needed.
+    pub fn add_edge(&mut self, from: N, to: N, weight: W) -> (NodeId, NodeId) {
+        let (from, to) = (self.node(from), self.node(to));
+        self.reverse[to].push((from, weight.clone()));
+        self.edges[from].push((to, weight));
+
+        (from, to)
+    }
+}
+
+impl<N, W> Graph<N, W> {
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// The id of `node`, if it's in the graph.
+    pub fn id<Q>(&self, node: &Q) -> Option<NodeId>
+    where
+        N: Borrow<Q> + Eq + Hash,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.ids.get(node).copied()
+    }
+
+    /// The node of `id`.
+    pub fn get(&self, id: NodeId) -> &N {
+        &self.nodes[id]
+    }
+
+    /// The nodes, by id.
+    pub fn nodes(&self) -> &[N] {
+        &self.nodes
+    }
+
+    /// The edges leaving `id`, with their target and weight, in order of insertion.
+    pub fn edges(&self, id: NodeId) -> &[(NodeId, W)] {
+        &self.edges[id]
+    }
+
+    /// The edges reaching `id`, with their source and weight, in order of insertion.
+    pub fn reverse_edges(&self, id: NodeId) -> &[(NodeId, W)] {
+        &self.reverse[id]
+    }
+
+    fn adjacency(&self, reverse: bool) -> &[Vec<(NodeId, W)>] {
+        if reverse {
+            &self.reverse
+        } else {
+            &self.edges
+        }
+    }
+
+    fn bfs_from(&self, starts: impl IntoIterator<Item = NodeId>, reverse: bool) -> Vec<NodeId> {
+        let adjacency = self.adjacency(reverse);
+        let mut seen = vec![false; self.len()];
+        let mut order = Vec::new();
+        let mut queue = VecDeque::new();
+
+        for start in starts {
+            if !seen[start] {
+                seen[start] = true;
+                queue.push_back(start);
+            }
+        }
+
+        while let Some(id) = queue.pop_front() {
+            order.push(id);
+            for &(next, _) in &adjacency[id] {
+                if !seen[next] {
+                    seen[next] = true;
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        order
+    }
+
+    /// The nodes reachable from `start`, itself included, in breadth-first order.
+    pub fn bfs(&self, start: NodeId) -> Vec<NodeId> {
+        self.bfs_from(Some(start), false)
+    }
+
+    /// The nodes reachable from `start`, itself included, in depth-first preorder.
+    pub fn dfs(&self, start: NodeId) -> Vec<NodeId> {
+        let mut seen = vec![false; self.len()];
+        let mut order = Vec::new();
+        let mut stack = vec![start];
+
+        while let Some(id) = stack.pop() {
+            if seen[id] {
+                continue;
+            }
+            seen[id] = true;
+            order.push(id);
+
+            // Reversed so that the first edge is explored first.
+            stack.extend(self.edges[id].iter().rev().map(|&(next, _)| next));
+        }
+
+        order
+    }
+
+    /// Whether there is a path from `from` to `to`.
+    pub fn reaches(&self, from: NodeId, to: NodeId) -> bool {
+        self.bfs(from).contains(&to)
+    }
+
+    /// The nodes at the end of a non-empty path from `id`.
+    pub fn descendants(&self, id: NodeId) -> Vec<NodeId> {
+        self.bfs_from(self.edges[id].iter().map(|&(next, _)| next), false)
+    }
+
+    /// The nodes at the start of a non-empty path to `id`.
+    pub fn ancestors(&self, id: NodeId) -> Vec<NodeId> {
+        self.bfs_from(self.reverse[id].iter().map(|&(prev, _)| prev), true)
+    }
+
+    /// The nodes reachable from `roots` in depth-first postorder, children before parents, or a
+    /// cycle among them.
+    fn post_order(&self, roots: impl IntoIterator<Item = NodeId>) -> Result<Vec<NodeId>, Cycle> {
+        #[derive(Clone, Copy, PartialEq)]
+        enum State {
+            New,
+            Open,
+            Done,
+        }
+
+        let mut state = vec![State::New; self.len()];
+        let mut order = Vec::new();
+
+        for root in roots {
+            if state[root] != State::New {
+                continue;
+            }
+
+            // The open nodes, each with the index of its next edge to explore.
+            let mut stack = vec![(root, 0)];
+            state[root] = State::Open;
+
+            while let Some(&mut (id, ref mut edge)) = stack.last_mut() {
+                match self.edges[id].get(*edge) {
+                    Some(&(next, _)) => {
+                        *edge += 1;
+                        match state[next] {
+                            State::New => {
+                                state[next] = State::Open;
+                                stack.push((next, 0));
+                            }
+                            State::Open => {
+                                let start = stack.iter().position(|&(n, _)| n == next).unwrap();
+                                let nodes = stack[start..].iter().map(|&(n, _)| n).collect();
+
+                                return Err(Cycle { nodes });
+                            }
+                            State::Done => {}
+                        }
+                    }
+                    None => {
+                        state[id] = State::Done;
+                        order.push(id);
+                        stack.pop();
+                    }
+                }
+            }
+        }
+
+        Ok(order)
+    }
+
+    /// The nodes ordered so that every edge goes forward, or a cycle if there is none.
+    pub fn topo_sort(&self) -> Result<Vec<NodeId>, Cycle> {
+        let mut order = self.post_order(0..self.len())?;
+        order.reverse();
+
+        Ok(order)
+    }
+
+    /// Computes the value of `root` from the values of its children, `f` being given each node
+    /// with its edges and the value of their targets. Each reachable node is computed once.
+    pub fn fold<T>(
+        &self,
+        root: NodeId,
+        mut f: impl FnMut(NodeId, &[(&W, &T)]) -> T,
+    ) -> Result<T, Cycle> {
+        let mut values: Vec<Option<T>> = (0..self.len()).map(|_| None).collect();
+
+        for id in self.post_order(Some(root))? {
+            let children = self.edges[id]
+                .iter()
+                .map(|(next, w)| (w, values[*next].as_ref().unwrap()))
+                .collect::<Vec<(&W, &T)>>();
+            let value = f(id, &children);
+            values[id] = Some(value);
+        }
+
+        Ok(values[root].take().unwrap())
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+impl<N: Display, W: Display> Graph<N, W> {
+    /// The graph in the Graphviz DOT language, the weights labelling the edges.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph {\n");
+
+        for (id, node) in self.nodes.iter().enumerate() {
+            dot.push_str(&format!(
+                "    {} [label=\"{}\"];\n",
+                id,
+                escape(&node.to_string())
+            ));
+        }
+        for (id, edges) in self.edges.iter().enumerate() {
+            for (next, w) in edges {
+                dot.push_str(&format!(
+                    "    {} -> {} [label=\"{}\"];\n",
+                    id,
+                    next,
+                    escape(&w.to_string())
+                ));
+            }
+        }
+        dot.push('}');
+
+        dot
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{Cycle, Graph};
+
+    /// a -> b -> d, a -> c -> d, d -> e, f alone.
+    fn diamond() -> Graph<&'static str, u32> {
+        let mut g = Graph::new();
+        for &(from, to, w) in &[("a", "b", 1), ("a", "c", 2), ("b", "d", 3), ("c", "d", 4)] {
+            g.add_edge(from, to, w);
+        }
+        g.add_edge("d", "e", 5);
+        g.node("f");
+
+        g
+    }
+
+    fn names(g: &Graph<&'static str, u32>, ids: Vec<usize>) -> Vec<&'static str> {
+        ids.into_iter().map(|id| *g.get(id)).collect()
+    }
+
+    #[test]
+    fn test_traversals() {
+        let mut g = diamond();
+        let a = g.id("a").unwrap();
+        let d = g.id("d").unwrap();
+
+        assert_eq!(6, g.len());
+        assert_eq!(a, g.node("a"));
+        assert_eq!(vec!["a", "b", "c", "d", "e"], names(&g, g.bfs(a)));
+        assert_eq!(vec!["a", "b", "d", "e", "c"], names(&g, g.dfs(a)));
+        assert_eq!(vec!["b", "c", "a"], names(&g, g.ancestors(d)));
+        assert_eq!(vec!["e"], names(&g, g.descendants(d)));
+        assert!(g.reaches(a, d));
+        assert!(!g.reaches(d, a));
+        assert_eq!(&[(1, 3), (2, 4)], g.reverse_edges(d));
+    }
+
+    #[test]
+    fn test_topo_sort() {
+        let mut g = diamond();
+        let order = g.topo_sort().unwrap();
+
+        assert_eq!(g.len(), order.len());
+        for id in 0..g.len() {
+            for &(next, _) in g.edges(id) {
+                let pos = |n| order.iter().position(|&o| o == n).unwrap();
+                assert!(pos(id) < pos(next));
+            }
+        }
+
+        g.add_edge("e", "b", 6);
+        let ids = |names: &[&str]| names.iter().map(|n| g.id(n).unwrap()).collect();
+        assert_eq!(
+            Err(Cycle {
+                nodes: ids(&["b", "d", "e"])
+            }),
+            g.topo_sort()
+        );
+    }
+
+    #[test]
+    fn test_fold() {
+        let mut g = diamond();
+        let mut calls = 0;
+        // Sum of the weights of the paths from a.
+        let total = g.fold(g.id("a").unwrap(), |_, children| {
+            calls += 1;
+            children.iter().map(|&(w, v)| w + v).sum::<u32>()
+        });
+
+        assert_eq!(Ok(1 + 2 + 3 + 4 + 5 * 2), total);
+        assert_eq!(5, calls);
+
+        g.add_edge("e", "a", 0);
+        assert!(g.fold(0, |_, _| 0).is_err());
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let mut g = Graph::new();
+        g.add_edge("x", "say \"hi\"", 3);
+
+        assert_eq!(
+            "digraph {\n    0 [label=\"x\"];\n    1 [label=\"say \\\"hi\\\"\"];\n    0 -> 1 [label=\"3\"];\n}",
+            g.to_dot()
+        );
+    }
+}
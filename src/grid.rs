@@ -0,0 +1,377 @@
+//! Flat, row-major grids of cells, indexed by `(row, column)`.
+
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::Diagnostic;
+
+/// A `(row, column)` position in a grid.
+pub type Pos = (usize, usize);
+
+/// Offsets of the 4 orthogonal neighbours, in reading order.
+pub const NEIGHBOURS_4: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+
+/// Offsets of the 8 neighbours, including the diagonal ones, in reading order.
+pub const NEIGHBOURS_8: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// A grid of `width` columns whose rows follow each other in `cells`.
+    ///
+    /// Panics when `cells` isn't a whole number of rows.
+    pub fn new(width: usize, cells: Vec<T>) -> Self {
+        let height = cells.len().checked_div(width).unwrap_or(0);
+        assert_eq!(
+            width * height,
+            cells.len(),
+            "{} cells don't make rows of {}",
+            cells.len(),
+            width
+        );
+
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parses a map of one row per line, each char becoming a cell through `cell`, which
+    /// returns `None` for invalid chars.
+    pub fn parse(
+        input: &[String],
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, Diagnostic> {
+        let width = input.first().map_or(0, |l| l.chars().count());
+        let mut cells = Vec::with_capacity(width * input.len());
+
+        for (idx, line) in input.iter().enumerate() {
+            if line.chars().count() != width {
+                return Err(Diagnostic::at_line(
+                    idx,
+                    line,
+                    format!("expected {} columns, found {}", width, line.chars().count()),
+                ));
+            }
+
+            for (col, c) in line.char_indices() {
+                match cell(c) {
+                    Some(cell) => cells.push(cell),
+                    None => {
+                        return Err(Diagnostic::new(
+                            idx,
+                            line,
+                            col..col + c.len_utf8(),
+                            format!("invalid char: '{}'", c),
+                        ))
+                    }
+                }
+            }
+        }
+
+        Ok(Grid {
+            width,
+            height: input.len(),
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The cells, row after row.
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub fn cells_mut(&mut self) -> &mut [T] {
+        &mut self.cells
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` rejects a size of 0, which only empty grids have.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Every position, in reading order.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |r| (0..width).map(move |c| (r, c)))
+    }
+
+    /// Index in `cells` of a position, which must be in the grid.
+    pub fn index_of(&self, (row, col): Pos) -> usize {
+        row * self.width + col
+    }
+
+    pub fn contains(&self, (row, col): Pos) -> bool {
+        row < self.height && col < self.width
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        if self.contains(pos) {
+            Some(&self.cells[self.index_of(pos)])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.contains(pos) {
+            let idx = self.index_of(pos);
+            Some(&mut self.cells[idx])
+        } else {
+            None
+        }
+    }
+
+    /// The cell at `row`, with the columns repeating to the right forever.
+    pub fn get_wrapping(&self, (row, col): Pos) -> Option<&T> {
+        if self.width == 0 {
+            return None;
+        }
+
+        self.get((row, col % self.width))
+    }
+
+    /// The cell at `(row, col)` on a torus: both the rows and the columns wrap around.
+    ///
+    /// Panics on empty grids.
+    pub fn get_toroidal(&self, (row, col): (isize, isize)) -> &T {
+        let row = row.rem_euclid(self.height as isize) as usize;
+        let col = col.rem_euclid(self.width as isize) as usize;
+
+        &self[(row, col)]
+    }
+
+    /// The position `delta` away from `pos`, if it's in the grid.
+    pub fn offset(&self, (row, col): Pos, (dr, dc): (isize, isize)) -> Option<Pos> {
+        let pos = (row.checked_add_signed(dr)?, col.checked_add_signed(dc)?);
+
+        if self.contains(pos) {
+            Some(pos)
+        } else {
+            None
+        }
+    }
+
+    /// The orthogonal neighbours of `pos` that are in the grid.
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        NEIGHBOURS_4
+            .iter()
+            .filter_map(move |&delta| self.offset(pos, delta))
+    }
+
+    /// The neighbours of `pos`, diagonal ones included, that are in the grid.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        NEIGHBOURS_8
+            .iter()
+            .filter_map(move |&delta| self.offset(pos, delta))
+    }
+
+    /// The positions seen from `pos` going in `direction`, up to the edge of the grid.
+    pub fn ray(&self, pos: Pos, direction: (isize, isize)) -> impl Iterator<Item = Pos> + '_ {
+        let mut pos = Some(pos);
+
+        std::iter::from_fn(move || {
+            pos = self.offset(pos?, direction);
+            pos
+        })
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// A `width` by `height` grid whose cell at each position is the one of `self` at `f` of it.
+    fn remap(&self, width: usize, height: usize, f: impl Fn(Pos) -> Pos) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..height)
+            .flat_map(|r| (0..width).map(move |c| (r, c)))
+            .map(|pos| self[f(pos)].clone())
+            .collect();
+
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Rows become columns.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.remap(self.height, self.width, |(r, c)| (c, r))
+    }
+
+    /// Rotates a quarter turn clockwise.
+    pub fn rotate_cw(&self) -> Self
+    where
+        T: Clone,
+    {
+        let h = self.height;
+        self.remap(self.height, self.width, |(r, c)| (h - 1 - c, r))
+    }
+
+    /// Rotates a quarter turn counterclockwise.
+    pub fn rotate_ccw(&self) -> Self
+    where
+        T: Clone,
+    {
+        let w = self.width;
+        self.remap(self.height, self.width, |(r, c)| (c, w - 1 - r))
+    }
+
+    /// Mirrors left to right.
+    pub fn flip_horizontal(&self) -> Self
+    where
+        T: Clone,
+    {
+        let w = self.width;
+        self.remap(self.width, self.height, |(r, c)| (r, w - 1 - c))
+    }
+
+    /// Mirrors top to bottom.
+    pub fn flip_vertical(&self) -> Self
+    where
+        T: Clone,
+    {
+        let h = self.height;
+        self.remap(self.width, self.height, |(r, c)| (h - 1 - r, c))
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        assert!(self.contains(pos), "{:?} is out of the grid", pos);
+        &self.cells[self.index_of(pos)]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        assert!(self.contains(pos), "{:?} is out of the grid", pos);
+        let idx = self.index_of(pos);
+        &mut self.cells[idx]
+    }
+}
+
+/// One line per row, without a trailing newline.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (r, row) in self.rows().enumerate() {
+            if r > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::grid::Grid;
+
+    fn grid(rows: &[&str]) -> Grid<char> {
+        let input = rows.iter().map(|r| r.to_string()).collect::<Vec<String>>();
+
+        Grid::parse(&input, Some).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let input = vec![String::from("#.#"), String::from(".x.")];
+        let err = Grid::parse(&input, |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })
+        .unwrap_err();
+        assert_eq!((2, 1..2), (err.line, err.columns));
+
+        let input = vec![String::from("#.#"), String::from("..")];
+        let err = Grid::parse(&input, Some).unwrap_err();
+        assert_eq!("expected 3 columns, found 2", err.message);
+
+        let g = grid(&["abc", "def"]);
+        assert_eq!((3, 2), (g.width(), g.height()));
+        assert_eq!("abc\ndef", g.to_string());
+        assert_eq!(0, Grid::parse(&[], Some).unwrap().height());
+    }
+
+    #[test]
+    fn test_indexing() {
+        let g = grid(&["abc", "def"]);
+
+        assert_eq!(Some(&'f'), g.get((1, 2)));
+        assert_eq!(None, g.get((2, 0)));
+        assert_eq!(Some(&'d'), g.get_wrapping((1, 6)));
+        assert_eq!(None, g.get_wrapping((2, 0)));
+        assert_eq!(&'f', g.get_toroidal((-1, -1)));
+        assert_eq!(&'a', g.get_toroidal((2, 3)));
+        assert_eq!(None, g.offset((0, 0), (-1, 0)));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let g = grid(&["abc", "def", "ghi"]);
+
+        assert_eq!(
+            vec![(0, 1), (1, 0)],
+            g.neighbours4((0, 0)).collect::<Vec<_>>()
+        );
+        assert_eq!(8, g.neighbours8((1, 1)).count());
+        assert_eq!(3, g.neighbours8((2, 2)).count());
+        assert_eq!(
+            vec![(1, 1), (2, 2)],
+            g.ray((0, 0), (1, 1)).collect::<Vec<_>>()
+        );
+        assert_eq!(0, g.ray((0, 0), (-1, 0)).count());
+    }
+
+    #[test]
+    fn test_transforms() {
+        let g = grid(&["abc", "def"]);
+
+        assert_eq!("da\neb\nfc", g.rotate_cw().to_string());
+        assert_eq!("cf\nbe\nad", g.rotate_ccw().to_string());
+        assert_eq!("ad\nbe\ncf", g.transpose().to_string());
+        assert_eq!("cba\nfed", g.flip_horizontal().to_string());
+        assert_eq!("def\nabc", g.flip_vertical().to_string());
+        assert_eq!(g, g.rotate_cw().rotate_cw().rotate_cw().rotate_cw());
+    }
+}
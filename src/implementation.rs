@@ -0,0 +1,14 @@
+use crate::Diagnostic;
+
+/// Solves one part of a day, returning the answer as text.
+pub type PartSolver = fn(&[String]) -> Result<String, Diagnostic>;
+
+/// A named way of solving one part of a day.
+///
+/// Days with several approaches list them in a `IMPLEMENTATIONS` constant, the first one of
+/// each part being the default.
+pub struct Implementation {
+    pub name: &'static str,
+    pub part: u8,
+    pub solve: PartSolver,
+}
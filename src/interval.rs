@@ -0,0 +1,291 @@
+//! Sets of integers stored as sorted, disjoint ranges.
+
+use std::fmt;
+use std::iter::FromIterator;
+use std::ops::{Bound, RangeBounds, RangeInclusive};
+
+/// Integers, which have a next and a previous value.
+pub trait Discrete: Copy + Ord {
+    const MIN: Self;
+    const MAX: Self;
+
+    fn succ(self) -> Option<Self>;
+
+    fn pred(self) -> Option<Self>;
+}
+
+macro_rules! discrete {
+    ($($t:ty),*) => {
+        $(
+            impl Discrete for $t {
+                const MIN: Self = <$t>::MIN;
+                const MAX: Self = <$t>::MAX;
+
+                fn succ(self) -> Option<Self> {
+                    self.checked_add(1)
+                }
+
+                fn pred(self) -> Option<Self> {
+                    self.checked_sub(1)
+                }
+            }
+        )*
+    };
+}
+
+discrete!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// A set of integers, kept as the fewest inclusive ranges: overlapping and adjacent ranges are
+/// merged as they are inserted.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    /// Sorted, disjoint and not adjacent.
+    ranges: Vec<(T, T)>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet { ranges: Vec::new() }
+    }
+}
+
+/// The inclusive bounds of `range`, `None` if it's empty.
+fn bounds<T: Discrete>(range: impl RangeBounds<T>) -> Option<(T, T)> {
+    let lo = match range.start_bound() {
+        Bound::Included(&lo) => lo,
+        Bound::Excluded(&lo) => lo.succ()?,
+        Bound::Unbounded => T::MIN,
+    };
+    let hi = match range.end_bound() {
+        Bound::Included(&hi) => hi,
+        Bound::Excluded(&hi) => hi.pred()?,
+        Bound::Unbounded => T::MAX,
+    };
+
+    if lo <= hi {
+        Some((lo, hi))
+    } else {
+        None
+    }
+}
+
+impl<T: Discrete> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Adds the values of `range`, e.g. `1..5` or `1..=4`.
+    pub fn insert(&mut self, range: impl RangeBounds<T>) {
+        let (mut lo, mut hi) = match bounds(range) {
+            Some(b) => b,
+            None => return,
+        };
+
+        // The ranges that overlap or touch the new one.
+        let start = self
+            .ranges
+            .partition_point(|&(_, h)| h.succ().is_some_and(|s| s < lo));
+        let end = self
+            .ranges
+            .partition_point(|&(l, _)| hi.succ().is_none_or(|s| l <= s));
+
+        if start < end {
+            lo = lo.min(self.ranges[start].0);
+            hi = hi.max(self.ranges[end - 1].1);
+        }
+        self.ranges.splice(start..end, Some((lo, hi)));
+    }
+
+    /// Removes the values of `range`.
+    pub fn remove(&mut self, range: impl RangeBounds<T>) {
+        let (lo, hi) = match bounds(range) {
+            Some(b) => b,
+            None => return,
+        };
+
+        let start = self.ranges.partition_point(|&(_, h)| h < lo);
+        let end = self.ranges.partition_point(|&(l, _)| l <= hi);
+        if start >= end {
+            return;
+        }
+
+        let mut rest = Vec::with_capacity(2);
+        let (first, last) = (self.ranges[start], self.ranges[end - 1]);
+        if first.0 < lo {
+            rest.push((first.0, lo.pred().unwrap()));
+        }
+        if last.1 > hi {
+            rest.push((hi.succ().unwrap(), last.1));
+        }
+        self.ranges.splice(start..end, rest);
+    }
+
+    /// Whether `value` is in the set, by binary search.
+    pub fn contains(&self, value: T) -> bool {
+        let idx = self.ranges.partition_point(|&(_, hi)| hi < value);
+
+        self.ranges.get(idx).is_some_and(|&(lo, _)| lo <= value)
+    }
+
+    /// The ranges of the set, in order.
+    pub fn ranges(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.ranges.iter().map(|&(lo, hi)| lo..=hi)
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut set = self.clone();
+        for &(lo, hi) in &other.ranges {
+            set.insert(lo..=hi);
+        }
+
+        set
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let (mut a, mut b) = (
+            self.ranges.iter().peekable(),
+            other.ranges.iter().peekable(),
+        );
+
+        while let (Some(&&(lo1, hi1)), Some(&&(lo2, hi2))) = (a.peek(), b.peek()) {
+            let (lo, hi) = (lo1.max(lo2), hi1.min(hi2));
+            if lo <= hi {
+                ranges.push((lo, hi));
+            }
+
+            if hi1 < hi2 {
+                a.next();
+            } else {
+                b.next();
+            }
+        }
+
+        IntervalSet { ranges }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut set = self.clone();
+        for &(lo, hi) in &other.ranges {
+            set.remove(lo..=hi);
+        }
+
+        set
+    }
+}
+
+impl<T: Discrete, R: RangeBounds<T>> FromIterator<R> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = R>>(iter: I) -> Self {
+        let mut set = IntervalSet::new();
+        for range in iter {
+            set.insert(range);
+        }
+
+        set
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for IntervalSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (lo, hi)) in self.ranges.iter().enumerate() {
+            if i > 0 {
+                write!(f, " or ")?;
+            }
+            write!(f, "{}-{}", lo, hi)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use crate::interval::IntervalSet;
+
+    fn ranges(set: &IntervalSet<i32>) -> Vec<(i32, i32)> {
+        set.ranges().map(|r| (*r.start(), *r.end())).collect()
+    }
+
+    #[test]
+    fn test_insert() {
+        let mut set = IntervalSet::new();
+        set.insert(10..=20);
+        set.insert(30..40);
+        assert_eq!(vec![(10, 20), (30, 39)], ranges(&set));
+
+        set.insert(21..25);
+        set.insert(26..=29);
+        assert_eq!(vec![(10, 24), (26, 39)], ranges(&set));
+
+        set.insert(0..=100);
+        assert_eq!(vec![(0, 100)], ranges(&set));
+
+        set.insert(5..5);
+        set.insert(..-5);
+        assert_eq!(vec![(i32::MIN, -6), (0, 100)], ranges(&set));
+        assert_eq!("-2147483648--6 or 0-100", set.to_string());
+
+        let mut full = IntervalSet::new();
+        full.insert(200u8..);
+        full.insert(..200);
+        assert_eq!(vec![0..=255], full.ranges().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut set = vec![0..=10, 20..=30]
+            .into_iter()
+            .collect::<IntervalSet<i32>>();
+        set.remove(5..25);
+        assert_eq!(vec![(0, 4), (25, 30)], ranges(&set));
+
+        set.remove(0..=4);
+        set.remove(27..=27);
+        assert_eq!(vec![(25, 26), (28, 30)], ranges(&set));
+    }
+
+    #[test]
+    fn test_against_brute_force() {
+        let sets = [
+            vec![1..=3, 5..=7],
+            vec![6..=11, 33..=44],
+            vec![0..=1, 4..=19, 3..=3],
+            vec![13..=40, 45..=50, 41..=44],
+        ];
+
+        for a in &sets {
+            for b in &sets {
+                let (sa, sb) = (
+                    a.iter().cloned().collect::<IntervalSet<i32>>(),
+                    b.iter().cloned().collect::<IntervalSet<i32>>(),
+                );
+                let (ba, bb) = (
+                    a.iter().cloned().flatten().collect::<BTreeSet<i32>>(),
+                    b.iter().cloned().flatten().collect::<BTreeSet<i32>>(),
+                );
+
+                for v in -1..=52 {
+                    assert_eq!(ba.contains(&v), sa.contains(v));
+                    assert_eq!(
+                        ba.contains(&v) || bb.contains(&v),
+                        sa.union(&sb).contains(v)
+                    );
+                    assert_eq!(
+                        ba.contains(&v) && bb.contains(&v),
+                        sa.intersection(&sb).contains(v)
+                    );
+                    assert_eq!(
+                        ba.contains(&v) && !bb.contains(&v),
+                        sa.difference(&sb).contains(v)
+                    );
+                }
+            }
+        }
+    }
+}
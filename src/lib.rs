@@ -1,28 +1,158 @@
-use std::fmt::Debug;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::env;
+use std::fmt::Display;
+use std::fs;
+use std::io::{self, ErrorKind};
 use std::ops::{Add, Mul, Sub};
+use std::process;
 use std::str::FromStr;
 
+pub mod assign;
+pub mod bitset;
+pub mod config;
+pub mod crypt;
+pub mod cycle;
+mod diagnostic;
+pub mod explain;
+pub mod gen;
+pub mod graph;
+pub mod grid;
+mod implementation;
+pub mod interval;
+pub mod math;
+pub mod memo;
+pub mod parse;
+pub mod point;
+
+pub use diagnostic::Diagnostic;
+pub use implementation::{Implementation, PartSolver};
+
+#[cfg(feature = "embed-inputs")]
+mod embedded {
+    include!(concat!(env!("OUT_DIR"), "/embedded_inputs.rs"));
+
+    pub fn get(filename: &str) -> Option<&'static [u8]> {
+        INPUTS
+            .iter()
+            .find(|(name, _)| *name == filename)
+            .map(|(_, data)| *data)
+    }
+}
+
+/// Returns the path given on the command line with `--input <path>` or `--input=<path>`.
+fn input_override() -> Option<String> {
+    let mut args = env::args().skip(1);
+
+    while let Some(arg) = args.next() {
+        if arg == "--input" {
+            return args.next();
+        }
+        if let Some(path) = arg.strip_prefix("--input=") {
+            return Some(String::from(path));
+        }
+    }
+
+    None
+}
+
+fn input_path(filename: &str) -> String {
+    input_override().unwrap_or_else(|| format!("{}/{}", config::get().input_dir, filename))
+}
+
+/// Returns the lines of the puzzle input `filename`.
+///
+/// The input is read from the `--input` path when one is given, or else located with
+/// `find_input`.
 pub fn get_input(filename: &str) -> Vec<String> {
-    let file = match File::open(format!("input/{}", filename)) {
-        Ok(file) => file,
-        Err(error) => panic!("Unable to open file {}: {}", filename, error),
-    };
+    match input_override() {
+        Some(path) => read_input(&path)
+            .unwrap_or_else(|error| panic!("Unable to open file {}: {}", path, error)),
+        None => find_input(filename)
+            .unwrap_or_else(|error| panic!("Unable to open file {}: {}", filename, error)),
+    }
+}
+
+/// Returns the lines of the puzzle input `filename`.
+///
+/// The input is taken from the inputs embedded with the `embed-inputs` feature, then from
+/// `<input_dir>/<filename>`, then from `<input_dir>/<filename>.enc`, `input_dir` being set
+/// in `aoc.toml`.
+pub fn find_input(filename: &str) -> io::Result<Vec<String>> {
+    #[cfg(feature = "embed-inputs")]
+    {
+        let encrypted = format!("{}.{}", filename, crypt::ENCRYPTED_EXT);
+        for name in &[filename, encrypted.as_str()] {
+            if let Some(data) = embedded::get(name) {
+                return decode_input(name, data.to_vec());
+            }
+        }
+    }
 
-    let reader = BufReader::new(file);
+    let path = format!("{}/{}", config::get().input_dir, filename);
 
-    reader.lines().map(|l| l.unwrap()).collect()
+    match read_input(&path) {
+        Err(error) if error.kind() == ErrorKind::NotFound => {
+            match read_input(&format!("{}.{}", path, crypt::ENCRYPTED_EXT)) {
+                Err(e) if e.kind() == ErrorKind::NotFound => Err(error),
+                res => res,
+            }
+        }
+        res => res,
+    }
+}
+
+/// Reads the lines of the input file at `path`, decrypting it when its name ends in `.enc`.
+pub fn read_input(path: &str) -> io::Result<Vec<String>> {
+    let data = fs::read(path)?;
+
+    decode_input(path, data)
+}
+
+fn decode_input(name: &str, data: Vec<u8>) -> io::Result<Vec<String>> {
+    let data = if name.ends_with(&format!(".{}", crypt::ENCRYPTED_EXT)) {
+        crypt::Key::load()
+            .and_then(|key| crypt::decrypt(&key, &data))
+            .map_err(|error| {
+                io::Error::new(
+                    ErrorKind::InvalidData,
+                    format!("unable to decrypt {}: {}", name, error),
+                )
+            })?
+    } else {
+        data
+    };
+
+    match String::from_utf8(data) {
+        Ok(text) => Ok(text.lines().map(String::from).collect()),
+        Err(error) => Err(io::Error::new(ErrorKind::InvalidData, error)),
+    }
 }
 
 pub fn get_input_as_int<T: Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Ord + FromStr>(
     filename: &str,
 ) -> Vec<T>
 where
-    <T as FromStr>::Err: Debug,
+    <T as FromStr>::Err: Display,
 {
-    get_input(filename)
+    parse_ints(&get_input(filename)).unwrap_or_else(|e| exit_with_diagnostic(e, filename))
+}
+
+/// Parses one number per line.
+pub fn parse_ints<T: FromStr>(input: &[String]) -> Result<Vec<T>, Diagnostic>
+where
+    <T as FromStr>::Err: Display,
+{
+    input
         .iter()
-        .map(|i| i.parse().unwrap())
+        .enumerate()
+        .map(|(idx, i)| {
+            i.parse()
+                .map_err(|e| Diagnostic::at_line(idx, i, format!("invalid number: {}", e)))
+        })
         .collect()
 }
+
+/// Prints a diagnostic about the given input file and exits.
+pub fn exit_with_diagnostic(diagnostic: Diagnostic, filename: &str) -> ! {
+    eprintln!("{}", diagnostic.with_file(&input_path(filename)));
+    process::exit(1);
+}
@@ -0,0 +1,397 @@
+//! Number theory: greatest common divisors, modular arithmetic, Chinese remainders and
+//! discrete logarithms.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+/// Unsigned integers with modular multiplication that can't overflow.
+pub trait ModInt: Copy + Eq {
+    const ZERO: Self;
+    const ONE: Self;
+
+    /// `self * other % m`.
+    fn mul_mod(self, other: Self, m: Self) -> Self;
+
+    fn rem(self, m: Self) -> Self;
+
+    fn is_odd(self) -> bool;
+
+    fn half(self) -> Self;
+}
+
+impl ModInt for u64 {
+    const ZERO: Self = 0;
+    const ONE: Self = 1;
+
+    fn mul_mod(self, other: Self, m: Self) -> Self {
+        (self as u128 * other as u128 % m as u128) as u64
+    }
+
+    fn rem(self, m: Self) -> Self {
+        self % m
+    }
+
+    fn is_odd(self) -> bool {
+        self & 1 == 1
+    }
+
+    fn half(self) -> Self {
+        self >> 1
+    }
+}
+
+impl ModInt for u128 {
+    const ZERO: Self = 0;
+    const ONE: Self = 1;
+
+    fn mul_mod(self, other: Self, m: Self) -> Self {
+        let (mut a, mut b) = (self % m, other % m);
+        if let Some(p) = a.checked_mul(b) {
+            return p % m;
+        }
+
+        // Double and add, each step staying below 2 * m.
+        let add = |x: u128, y: u128| if x >= m - y { x - (m - y) } else { x + y };
+        let mut res = 0;
+        while b > 0 {
+            if b & 1 == 1 {
+                res = add(res, a);
+            }
+            a = add(a, a);
+            b >>= 1;
+        }
+
+        res
+    }
+
+    fn rem(self, m: Self) -> Self {
+        self % m
+    }
+
+    fn is_odd(self) -> bool {
+        self & 1 == 1
+    }
+
+    fn half(self) -> Self {
+        self >> 1
+    }
+}
+
+/// `a * b % m`, without overflowing.
+pub fn mod_mul<T: ModInt>(a: T, b: T, m: T) -> T {
+    a.mul_mod(b, m)
+}
+
+/// `base ^ exp % m`, by squaring.
+pub fn mod_pow<T: ModInt>(base: T, mut exp: T, m: T) -> T {
+    let mut base = base.rem(m);
+    let mut res = T::ONE.rem(m);
+
+    while exp != T::ZERO {
+        if exp.is_odd() {
+            res = res.mul_mod(base, m);
+        }
+        base = base.mul_mod(base, m);
+        exp = exp.half();
+    }
+
+    res
+}
+
+pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        let r = a % b;
+        a = b;
+        b = r;
+    }
+
+    a
+}
+
+/// Least common multiple, `None` if it overflows.
+pub fn lcm(a: u64, b: u64) -> Option<u64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+/// `(g, x, y)` such that `a * x + b * y = g`, the greatest common divisor of `a` and `b`.
+pub fn ext_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut r0, mut r1) = (a, b);
+    let (mut x0, mut x1) = (1, 0);
+    let (mut y0, mut y1) = (0, 1);
+
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (x0, x1) = (x1, x0 - q * x1);
+        (y0, y1) = (y1, y0 - q * y1);
+    }
+
+    if r0 < 0 {
+        (-r0, -x0, -y0)
+    } else {
+        (r0, x0, y0)
+    }
+}
+
+/// The inverse of `a` modulo `m`, if `a` and `m` are coprime.
+pub fn mod_inv(a: u64, m: u64) -> Option<u64> {
+    let (g, x, _) = ext_gcd(a as i128, m as i128);
+    if g != 1 {
+        return None;
+    }
+
+    Some(x.rem_euclid(m as i128) as u64)
+}
+
+/// Error returned when congruences can't be combined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrtError {
+    /// No number satisfies every congruence, e.g. `x = 1 mod 4` and `x = 2 mod 6`.
+    Inconsistent,
+    /// The least common multiple of the moduli doesn't fit in a `u64`.
+    Overflow,
+}
+
+impl fmt::Display for CrtError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CrtError::Inconsistent => write!(f, "inconsistent congruences"),
+            CrtError::Overflow => write!(f, "the moduli have a too large common multiple"),
+        }
+    }
+}
+
+impl Error for CrtError {}
+
+/// Combines the congruences `x = residue mod modulus` into the single `(residue, modulus)` they
+/// are equivalent to, the modulus being the least common multiple of theirs.
+///
+/// The moduli don't need to be coprime. The moduli must not be 0.
+pub fn crt(congruences: impl IntoIterator<Item = (u64, u64)>) -> Result<(u64, u64), CrtError> {
+    let (mut r, mut m) = (0u64, 1u64);
+
+    for (r2, m2) in congruences {
+        let r2 = r2 % m2;
+        let g = gcd(m, m2);
+        let diff = r2 as i128 - r as i128;
+        if diff % g as i128 != 0 {
+            return Err(CrtError::Inconsistent);
+        }
+
+        let l = lcm(m, m2).ok_or(CrtError::Overflow)?;
+        // m * k = diff mod m2, with m / g invertible modulo m2 / g.
+        let m2g = m2 / g;
+        let inv = mod_inv((m / g) % m2g, m2g).unwrap_or(0);
+        let k = ((diff / g as i128).rem_euclid(m2g as i128) as u128 * inv as u128) % m2g as u128;
+
+        r = ((r as u128 + m as u128 * k) % l as u128) as u64;
+        m = l;
+    }
+
+    Ok((r, m))
+}
+
+/// Smallest `r` such that `r * r >= n`, exact even where `f64` rounds `n`.
+pub fn ceil_sqrt(n: u64) -> u64 {
+    let square = |r: u64| r as u128 * r as u128;
+
+    let mut r = (n as f64).sqrt() as u64;
+    while square(r) < n as u128 {
+        r += 1;
+    }
+    while r > 0 && square(r - 1) >= n as u128 {
+        r -= 1;
+    }
+
+    r
+}
+
+/// Smallest `x` such that `base ^ x = target mod m`, by baby-step giant-step.
+///
+/// `base` must be invertible modulo `m`, e.g. any non-multiple of a prime `m`, otherwise only
+/// the `x` below `sqrt(m)` are found.
+pub fn discrete_log(base: u64, target: u64, m: u64) -> Option<u64> {
+    if m == 1 {
+        return Some(0);
+    }
+
+    let target = target % m;
+    let n = ceil_sqrt(m);
+    assert!(n as u128 * n as u128 >= m as u128);
+
+    // base ^ j for j < n, keeping the smallest j of each value.
+    let mut baby = HashMap::with_capacity(n as usize);
+    let mut value = 1;
+    for j in 0..n {
+        baby.entry(value).or_insert(j);
+        value = mod_mul(value, base, m);
+    }
+    if let Some(&j) = baby.get(&target) {
+        return Some(j);
+    }
+
+    let giant = mod_pow(mod_inv(base, m)?, n, m);
+    let mut gamma = target;
+    for i in 0..n {
+        if let Some(j) = baby.get(&gamma) {
+            return Some(i * n + j);
+        }
+        gamma = mod_mul(gamma, giant, m);
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::math::{
+        ceil_sqrt, crt, discrete_log, ext_gcd, lcm, mod_inv, mod_mul, mod_pow, CrtError,
+    };
+
+    #[test]
+    fn test_mod_mul() {
+        let m = u64::MAX - 58;
+        assert_eq!(
+            ((m - 1) as u128 * (m - 2) as u128 % m as u128) as u64,
+            mod_mul(m - 1, m - 2, m)
+        );
+        // (-1) * (-1) = 1
+        let m = u128::MAX - 158;
+        assert_eq!(1, mod_mul(m - 1, m - 1, m));
+        assert_eq!(2, mod_mul(m - 1, m - 2, m));
+
+        for a in 0..50u64 {
+            for m in 1..20u64 {
+                let brute = (0..a % 7).fold(1 % m, |acc, _| acc * a % m);
+                assert_eq!(brute, mod_pow(a, a % 7, m), "{}^{} mod {}", a, a % 7, m);
+                assert_eq!(
+                    brute as u128,
+                    mod_pow(a as u128, (a % 7) as u128, m as u128)
+                );
+            }
+        }
+        assert_eq!(1 << 127, mod_pow(2u128, 127, u128::MAX));
+        assert_eq!(1, mod_pow(2u128, 128, u128::MAX));
+    }
+
+    #[test]
+    fn test_gcd() {
+        for a in -30..30i128 {
+            for b in -30..30i128 {
+                let (g, x, y) = ext_gcd(a, b);
+                assert_eq!(g, a * x + b * y, "ext_gcd({}, {})", a, b);
+                assert!(g >= 0);
+                if g != 0 {
+                    assert_eq!((0, 0), (a % g, b % g));
+                }
+            }
+        }
+
+        for m in 1..40u64 {
+            for a in 0..40u64 {
+                let brute = (0..m).find(|x| a * x % m == 1 % m);
+                assert_eq!(brute, mod_inv(a, m), "{}^-1 mod {}", a, m);
+            }
+        }
+        assert_eq!(None, lcm(u64::MAX, u64::MAX - 1));
+    }
+
+    #[test]
+    fn test_crt() {
+        for m1 in 1..13u64 {
+            for m2 in 1..13u64 {
+                for r1 in 0..m1 {
+                    for r2 in 0..m2 {
+                        let l = lcm(m1, m2).unwrap();
+                        let brute = (0..l).find(|x| x % m1 == r1 && x % m2 == r2);
+                        let res = crt([(r1, m1), (r2, m2)].iter().copied());
+                        match brute {
+                            Some(x) => assert_eq!(Ok((x, l)), res),
+                            None => assert_eq!(Err(CrtError::Inconsistent), res),
+                        }
+                    }
+                }
+            }
+        }
+
+        assert_eq!(Ok((0, 1)), crt(vec![]));
+        assert_eq!(
+            Ok((1068781, 3162341)),
+            crt(vec![(0, 7), (12, 13), (55, 59), (25, 31), (12, 19)])
+        );
+        assert_eq!(
+            Err(CrtError::Overflow),
+            crt(vec![(0, u64::MAX), (0, u64::MAX - 1)])
+        );
+    }
+
+    #[test]
+    fn test_ceil_sqrt() {
+        for n in 0..1000u64 {
+            let r = ceil_sqrt(n);
+            assert!(r * r >= n && (r == 0 || (r - 1) * (r - 1) < n), "{}", n);
+        }
+
+        // Squares around 2^53 and above, where the f64 square root rounds.
+        for r in [(1u64 << 27) + 1, 94906267, 3037000499, u32::MAX as u64] {
+            assert_eq!(r, ceil_sqrt(r * r));
+            assert_eq!(r + 1, ceil_sqrt(r * r + 1));
+            assert_eq!(r, ceil_sqrt(r * r - 1));
+        }
+        assert_eq!(1 << 32, ceil_sqrt(u64::MAX));
+    }
+
+    #[test]
+    fn test_discrete_log() {
+        for &m in &[2u64, 7, 11, 13, 101] {
+            for base in 1..m {
+                for target in 0..m {
+                    let brute = (0..m).find(|&x| mod_pow(base, x, m) == target);
+                    assert_eq!(
+                        brute,
+                        discrete_log(base, target, m),
+                        "log_{} {} mod {}",
+                        base,
+                        target,
+                        m
+                    );
+                }
+            }
+        }
+
+        // Bases that aren't invertible only find the logarithms below sqrt(m).
+        for &m in &[4u64, 6, 8, 9, 12, 15, 16, 18] {
+            let n = ceil_sqrt(m);
+            for base in 0..m {
+                for target in 0..m {
+                    let brute = (0..m).find(|&x| mod_pow(base, x, m) == target);
+                    let expected = if mod_inv(base, m).is_some() {
+                        brute
+                    } else {
+                        brute.filter(|&x| x < n)
+                    };
+                    assert_eq!(
+                        expected,
+                        discrete_log(base, target, m),
+                        "log_{} {} mod {}",
+                        base,
+                        target,
+                        m
+                    );
+                }
+            }
+        }
+        assert_eq!(Some(0), discrete_log(2, 1, 4));
+        assert_eq!(Some(1), discrete_log(2, 2, 4));
+        assert_eq!(None, discrete_log(2, 3, 4));
+
+        // The public key of the day 25 example.
+        assert_eq!(Some(8), discrete_log(7, 5764801, 20201227));
+        assert_eq!(Some(11), discrete_log(7, 17807724, 20201227));
+    }
+}
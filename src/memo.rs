@@ -0,0 +1,135 @@
+//! Memoization of function calls, in a cache owned by the caller rather than a global one.
+//!
+//! Recursive functions take the cache as an argument and compute missing values through
+//! `get_or_insert_with`, which hands the cache back for the recursive calls:
+//!
+//! ```
+//! use aoc_2020::memo::Memo;
+//!
+//! fn fib(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+//!     if n < 2 {
+//!         return n;
+//!     }
+//!
+//!     memo.get_or_insert_with(n, |memo| fib(memo, n - 1) + fib(memo, n - 2))
+//! }
+//!
+//! let mut memo = Memo::new();
+//! assert_eq!(12586269025, fib(&mut memo, 50));
+//! assert_eq!((47, 49), (memo.hits(), memo.misses()));
+//! ```
+
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// The values computed for each key, and how often they were looked up.
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    values: HashMap<K, V>,
+    hits: usize,
+    misses: usize,
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Memo {
+            values: HashMap::new(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+}
+
+impl<K: Eq + Hash, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The value of `key`, computed by `f` if it's missing. `f` is given the cache, so that it
+    /// can make recursive calls through it.
+    pub fn get_or_insert_with(&mut self, key: K, f: impl FnOnce(&mut Self) -> V) -> V {
+        if let Some(v) = self.values.get(&key) {
+            self.hits += 1;
+            return v.clone();
+        }
+
+        self.misses += 1;
+        let v = f(self);
+        self.values.insert(key, v.clone());
+
+        v
+    }
+
+    /// The value of `key`, if it was computed, without counting the lookup.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.values.get(key)
+    }
+
+    /// Number of values computed.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Number of lookups that found their value.
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    /// Number of lookups that had to compute their value.
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+
+    /// Forgets the values and the statistics.
+    pub fn clear(&mut self) {
+        self.values.clear();
+        self.hits = 0;
+        self.misses = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::memo::Memo;
+
+    /// Number of ways to climb `n` steps, 1 or 2 at a time, counting the calls to `f`.
+    fn stairs(memo: &mut Memo<u32, u64>, calls: &mut u32, n: u32) -> u64 {
+        *calls += 1;
+        if n < 2 {
+            return 1;
+        }
+
+        memo.get_or_insert_with(n, |memo| {
+            stairs(memo, calls, n - 1) + stairs(memo, calls, n - 2)
+        })
+    }
+
+    #[test]
+    fn test_memo() {
+        let mut memo = Memo::new();
+        let mut calls = 0;
+
+        assert_eq!(10946, stairs(&mut memo, &mut calls, 20));
+        assert_eq!(39, calls);
+        assert_eq!((17, 19), (memo.hits(), memo.misses()));
+        assert_eq!(19, memo.len());
+        assert_eq!(Some(&89), memo.get(&10));
+
+        assert_eq!(10946, stairs(&mut memo, &mut calls, 20));
+        assert_eq!((18, 19), (memo.hits(), memo.misses()));
+
+        memo.clear();
+        assert!(memo.is_empty());
+        assert_eq!((0, 0), (memo.hits(), memo.misses()));
+        assert_eq!(None, memo.get(&10));
+    }
+}
@@ -0,0 +1,369 @@
+//! Parser combinators for the lines of puzzle inputs.
+//!
+//! A parser reads the start of a line from a byte position and returns the value it parsed
+//! with the position after it, or an error pointing at the offending columns. Tuples of parsers
+//! parse in sequence:
+//!
+//! ```
+//! use aoc_2020::parse::{letter, literal, parse_line, uint, word, Parser};
+//!
+//! let entry = (uint::<u32>(), literal("-"), uint::<u32>(), literal(" "), letter(), literal(": "), word())
+//!     .map(|(min, _, max, _, c, _, password)| (min, max, c, password));
+//!
+//! assert_eq!((1, 3, 'a', String::from("abcde")), parse_line(&entry, 0, "1-3 a: abcde").unwrap());
+//! assert_eq!(2..3, parse_line(&entry, 0, "1-x a: abcde").unwrap_err().columns);
+//! ```
+
+use std::fmt::Display;
+use std::ops::Range;
+use std::str::FromStr;
+
+use crate::Diagnostic;
+
+/// What a parser expected, and where.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Byte range of the offending text in the line.
+    pub columns: Range<usize>,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(columns: Range<usize>, message: impl Into<String>) -> Self {
+        ParseError {
+            columns,
+            message: message.into(),
+        }
+    }
+
+    /// The error for `what` missing at `pos`, pointing at the char found there.
+    fn expected(line: &str, pos: usize, what: &str) -> Self {
+        let end = line[pos..]
+            .chars()
+            .next()
+            .map_or(pos, |c| pos + c.len_utf8());
+
+        ParseError::new(pos..end, format!("expected {}", what))
+    }
+}
+
+pub type ParseResult<T> = Result<(T, usize), ParseError>;
+
+pub trait Parser<T> {
+    /// Parses the start of `line[pos..]`, returning the value and the position after it.
+    fn parse_at(&self, line: &str, pos: usize) -> ParseResult<T>;
+
+    fn map<U>(self, f: impl Fn(T) -> U) -> impl Parser<U>
+    where
+        Self: Sized,
+    {
+        move |line: &str, pos: usize| {
+            let (value, next) = self.parse_at(line, pos)?;
+            Ok((f(value), next))
+        }
+    }
+
+    /// Converts the value with `f`, whose error points at the parsed text.
+    fn try_map<U, E: Display>(self, f: impl Fn(T) -> Result<U, E>) -> impl Parser<U>
+    where
+        Self: Sized,
+    {
+        move |line: &str, pos: usize| {
+            let (value, next) = self.parse_at(line, pos)?;
+            match f(value) {
+                Ok(value) => Ok((value, next)),
+                Err(e) => Err(ParseError::new(pos..next, e.to_string())),
+            }
+        }
+    }
+
+    /// Tries `other` when this parser fails, keeping the error that got the furthest if both
+    /// fail.
+    fn or(self, other: impl Parser<T>) -> impl Parser<T>
+    where
+        Self: Sized,
+    {
+        move |line: &str, pos: usize| match self.parse_at(line, pos) {
+            Ok(res) => Ok(res),
+            Err(e1) => other.parse_at(line, pos).map_err(|e2| {
+                if e2.columns.start > e1.columns.start {
+                    e2
+                } else {
+                    e1
+                }
+            }),
+        }
+    }
+
+    /// Parses `next` after this parser, keeping only the value of this one.
+    fn skip<U>(self, next: impl Parser<U>) -> impl Parser<T>
+    where
+        Self: Sized,
+    {
+        move |line: &str, pos: usize| {
+            let (value, pos) = self.parse_at(line, pos)?;
+            let (_, pos) = next.parse_at(line, pos)?;
+            Ok((value, pos))
+        }
+    }
+}
+
+impl<T, F> Parser<T> for F
+where
+    F: Fn(&str, usize) -> ParseResult<T>,
+{
+    fn parse_at(&self, line: &str, pos: usize) -> ParseResult<T> {
+        self(line, pos)
+    }
+}
+
+macro_rules! sequence {
+    ($($p:ident $t:ident $v:ident),+) => {
+        impl<$($t, $p: Parser<$t>),+> Parser<($($t,)+)> for ($($p,)+) {
+            fn parse_at(&self, line: &str, pos: usize) -> ParseResult<($($t,)+)> {
+                let ($($v,)+) = self;
+                $(let ($v, pos) = $v.parse_at(line, pos)?;)+
+                Ok((($($v,)+), pos))
+            }
+        }
+    };
+}
+
+sequence!(P1 T1 v1, P2 T2 v2);
+sequence!(P1 T1 v1, P2 T2 v2, P3 T3 v3);
+sequence!(P1 T1 v1, P2 T2 v2, P3 T3 v3, P4 T4 v4);
+sequence!(P1 T1 v1, P2 T2 v2, P3 T3 v3, P4 T4 v4, P5 T5 v5);
+sequence!(P1 T1 v1, P2 T2 v2, P3 T3 v3, P4 T4 v4, P5 T5 v5, P6 T6 v6);
+sequence!(P1 T1 v1, P2 T2 v2, P3 T3 v3, P4 T4 v4, P5 T5 v5, P6 T6 v6, P7 T7 v7);
+
+/// Exactly `s`.
+pub fn literal(s: &'static str) -> impl Parser<&'static str> {
+    move |line: &str, pos: usize| {
+        if line[pos..].starts_with(s) {
+            Ok((s, pos + s.len()))
+        } else {
+            Err(ParseError::expected(line, pos, &format!("`{}`", s)))
+        }
+    }
+}
+
+/// One of `words`, the first one that matches.
+pub fn one_of(words: &'static [&'static str]) -> impl Parser<&'static str> {
+    move |line: &str, pos: usize| {
+        if let Some(w) = words.iter().find(|w| line[pos..].starts_with(*w)) {
+            return Ok((*w, pos + w.len()));
+        }
+
+        let expected = words
+            .iter()
+            .map(|w| format!("`{}`", w))
+            .collect::<Vec<String>>()
+            .join(", ");
+        let end = pos
+            + line[pos..]
+                .find(|c: char| !c.is_alphanumeric())
+                .unwrap_or(line.len() - pos);
+        if end == pos {
+            return Err(ParseError::expected(
+                line,
+                pos,
+                &format!("one of {}", expected),
+            ));
+        }
+
+        Err(ParseError::new(
+            pos..end,
+            format!(
+                "unexpected `{}`, expected one of {}",
+                &line[pos..end],
+                expected
+            ),
+        ))
+    }
+}
+
+/// The longest non-empty run of chars matching `pred`, described by `what` in errors.
+pub fn take_while1(pred: impl Fn(char) -> bool, what: &'static str) -> impl Parser<String> {
+    move |line: &str, pos: usize| {
+        let len = line[pos..].find(|c| !pred(c)).unwrap_or(line.len() - pos);
+        if len == 0 {
+            return Err(ParseError::expected(line, pos, what));
+        }
+
+        Ok((line[pos..pos + len].to_owned(), pos + len))
+    }
+}
+
+/// A run of ASCII letters.
+pub fn word() -> impl Parser<String> {
+    take_while1(|c| c.is_ascii_alphabetic(), "a word")
+}
+
+/// A single ASCII letter.
+pub fn letter() -> impl Parser<char> {
+    move |line: &str, pos: usize| match line[pos..].chars().next() {
+        Some(c) if c.is_ascii_alphabetic() => Ok((c, pos + 1)),
+        _ => Err(ParseError::expected(line, pos, "a letter")),
+    }
+}
+
+/// An unsigned decimal number, which must fit in `T`.
+pub fn uint<T: FromStr>() -> impl Parser<T>
+where
+    T::Err: Display,
+{
+    take_while1(|c| c.is_ascii_digit(), "a number").try_map(|digits| {
+        digits
+            .parse::<T>()
+            .map_err(|e| format!("invalid number: {}", e))
+    })
+}
+
+/// A decimal number with an optional sign, which must fit in `T`.
+pub fn int<T: FromStr>() -> impl Parser<T>
+where
+    T::Err: Display,
+{
+    move |line: &str, pos: usize| {
+        let (_, next) = (
+            optional(literal("+").or(literal("-"))),
+            take_while1(|c| c.is_ascii_digit(), "a number"),
+        )
+            .parse_at(line, pos)?;
+
+        match line[pos..next].parse::<T>() {
+            Ok(n) => Ok((n, next)),
+            Err(e) => Err(ParseError::new(pos..next, format!("invalid number: {}", e))),
+        }
+    }
+}
+
+/// The value of `p` if it matches, `None` otherwise.
+pub fn optional<T>(p: impl Parser<T>) -> impl Parser<Option<T>> {
+    move |line: &str, pos: usize| match p.parse_at(line, pos) {
+        Ok((value, next)) => Ok((Some(value), next)),
+        Err(_) => Ok((None, pos)),
+    }
+}
+
+/// One or more values of `p` separated by `sep`.
+pub fn separated<T, S>(p: impl Parser<T>, sep: impl Parser<S>) -> impl Parser<Vec<T>> {
+    move |line: &str, pos: usize| {
+        let (first, mut pos) = p.parse_at(line, pos)?;
+        let mut values = vec![first];
+
+        while let Ok((_, next)) = sep.parse_at(line, pos) {
+            let (value, next) = p.parse_at(line, next)?;
+            values.push(value);
+            pos = next;
+        }
+
+        Ok((values, pos))
+    }
+}
+
+/// The rest of the line, which may be empty.
+pub fn rest() -> impl Parser<String> {
+    move |line: &str, pos: usize| Ok((line[pos..].to_owned(), line.len()))
+}
+
+/// Parses the whole `line`, at index `idx` of the input.
+pub fn parse_line<T>(p: &impl Parser<T>, idx: usize, line: &str) -> Result<T, Diagnostic> {
+    parse_str(p, line).map_err(|e| Diagnostic::new(idx, line, e.columns, e.message))
+}
+
+/// Parses the whole of `s`.
+pub fn parse_str<T>(p: &impl Parser<T>, s: &str) -> Result<T, ParseError> {
+    let (value, pos) = p.parse_at(s, 0)?;
+    if pos < s.len() {
+        return Err(ParseError::new(
+            pos..s.len(),
+            format!("unexpected `{}`", &s[pos..]),
+        ));
+    }
+
+    Ok(value)
+}
+
+/// Parses every line of `input` with `p`.
+pub fn parse_lines<T>(p: &impl Parser<T>, input: &[String]) -> Result<Vec<T>, Diagnostic> {
+    input
+        .iter()
+        .enumerate()
+        .map(|(idx, line)| parse_line(p, idx, line))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse::{
+        int, literal, one_of, optional, parse_line, parse_str, separated, uint, word, Parser,
+    };
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(Ok(42u8), parse_str(&uint(), "42"));
+        assert_eq!(Ok(-17i32), parse_str(&int(), "-17"));
+        assert_eq!(Ok(17i32), parse_str(&int(), "+17"));
+
+        let err = parse_str(&uint::<u8>(), "256").unwrap_err();
+        assert_eq!(0..3, err.columns);
+        assert_eq!(
+            "invalid number: number too large to fit in target type",
+            err.message
+        );
+
+        let err = parse_str(&uint::<u8>(), "x").unwrap_err();
+        assert_eq!(
+            (0..1, "expected a number"),
+            (err.columns, err.message.as_str())
+        );
+        assert_eq!(1..1, parse_str(&int::<i8>(), "-").unwrap_err().columns);
+    }
+
+    #[test]
+    fn test_combinators() {
+        let list = separated(uint::<u32>(), literal(","));
+        assert_eq!(Ok(vec![1, 22, 333]), parse_str(&list, "1,22,333"));
+        assert_eq!(4..5, parse_str(&list, "1,2,x").unwrap_err().columns);
+
+        let bags = (
+            uint::<u32>(),
+            literal(" "),
+            word(),
+            literal(" bag"),
+            optional(literal("s")),
+        );
+        assert_eq!(
+            Ok((2, " ", String::from("red"), " bag", Some("s"))),
+            parse_str(&bags, "2 red bags")
+        );
+        assert_eq!(
+            Ok((1, " ", String::from("red"), " bag", None)),
+            parse_str(&bags, "1 red bag")
+        );
+
+        let op = one_of(&["nop", "acc"]).skip(literal(" ")).map(String::from);
+        assert_eq!(Ok(String::from("acc")), parse_str(&op, "acc "));
+        let err = parse_str(&op, "jmp +1").unwrap_err();
+        assert_eq!(0..3, err.columns);
+        assert_eq!(
+            "unexpected `jmp`, expected one of `nop`, `acc`",
+            err.message
+        );
+
+        let either = (literal("a"), literal("b")).or((literal("a"), literal("c")));
+        assert_eq!(1..2, parse_str(&either, "ad").unwrap_err().columns);
+        assert_eq!(2..3, parse_str(&either, "acd").unwrap_err().columns);
+    }
+
+    #[test]
+    fn test_parse_line() {
+        let even = uint::<u32>().try_map(|n| if n % 2 == 0 { Ok(n) } else { Err("odd") });
+        let err = parse_line(&(literal("n="), even), 4, "n=13").unwrap_err();
+
+        assert_eq!(5, err.line);
+        assert_eq!(2..4, err.columns);
+        assert_eq!("odd", err.message);
+    }
+}
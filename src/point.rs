@@ -0,0 +1,313 @@
+//! Points of integer coordinates in any number of dimensions, and the compass directions of
+//! the plane.
+//!
+//! `Point2` has `x` pointing east and `y` pointing north, so a left turn is counterclockwise.
+
+use std::fmt;
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub, SubAssign};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PointN<const N: usize>(pub [i64; N]);
+
+pub type Point2 = PointN<2>;
+pub type Point3 = PointN<3>;
+
+impl<const N: usize> PointN<N> {
+    pub const ORIGIN: Self = PointN([0; N]);
+
+    /// Sum of the absolute values of the coordinates.
+    pub fn norm1(&self) -> i64 {
+        self.0.iter().map(|c| c.abs()).sum()
+    }
+
+    pub fn manhattan(&self, other: Self) -> i64 {
+        (*self - other).norm1()
+    }
+
+    /// The `3^N - 1` points around this one, diagonal ones included.
+    pub fn neighbours(self) -> impl Iterator<Item = Self> {
+        let count = 3usize.pow(N as u32);
+
+        // Each index, written in base 3, gives the offsets -1, 0 or 1 of the coordinates.
+        (0..count)
+            .filter(move |&i| i != count / 2)
+            .map(move |mut i| {
+                let mut p = self;
+                for c in p.0.iter_mut() {
+                    *c += (i % 3) as i64 - 1;
+                    i /= 3;
+                }
+                p
+            })
+    }
+
+    /// The `2N` points one unit away along a single axis.
+    pub fn orthogonal_neighbours(self) -> impl Iterator<Item = Self> {
+        (0..N).flat_map(move |axis| {
+            [-1, 1].iter().map(move |d| {
+                let mut p = self;
+                p.0[axis] += d;
+                p
+            })
+        })
+    }
+}
+
+impl Point2 {
+    pub const fn new(x: i64, y: i64) -> Self {
+        PointN([x, y])
+    }
+
+    pub fn x(&self) -> i64 {
+        self.0[0]
+    }
+
+    pub fn y(&self) -> i64 {
+        self.0[1]
+    }
+
+    /// Rotates around the origin by `quarters` quarter turns, counterclockwise when positive.
+    pub fn rotate(self, quarters: i64) -> Self {
+        let (x, y) = (self.x(), self.y());
+
+        match quarters.rem_euclid(4) {
+            0 => self,
+            1 => Point2::new(-y, x),
+            2 => Point2::new(-x, -y),
+            _ => Point2::new(y, -x),
+        }
+    }
+
+    pub fn turn(self, turn: Turn) -> Self {
+        self.rotate(turn.quarters())
+    }
+}
+
+impl Point3 {
+    pub const fn new(x: i64, y: i64, z: i64) -> Self {
+        PointN([x, y, z])
+    }
+
+    pub fn x(&self) -> i64 {
+        self.0[0]
+    }
+
+    pub fn y(&self) -> i64 {
+        self.0[1]
+    }
+
+    pub fn z(&self) -> i64 {
+        self.0[2]
+    }
+}
+
+impl<const N: usize> Default for PointN<N> {
+    fn default() -> Self {
+        Self::ORIGIN
+    }
+}
+
+impl<const N: usize> From<[i64; N]> for PointN<N> {
+    fn from(coords: [i64; N]) -> Self {
+        PointN(coords)
+    }
+}
+
+impl<const N: usize> Index<usize> for PointN<N> {
+    type Output = i64;
+
+    fn index(&self, axis: usize) -> &i64 {
+        &self.0[axis]
+    }
+}
+
+impl<const N: usize> IndexMut<usize> for PointN<N> {
+    fn index_mut(&mut self, axis: usize) -> &mut i64 {
+        &mut self.0[axis]
+    }
+}
+
+impl<const N: usize> Add for PointN<N> {
+    type Output = Self;
+
+    fn add(mut self, other: Self) -> Self {
+        self += other;
+        self
+    }
+}
+
+impl<const N: usize> AddAssign for PointN<N> {
+    fn add_assign(&mut self, other: Self) {
+        for (c, o) in self.0.iter_mut().zip(other.0.iter()) {
+            *c += o;
+        }
+    }
+}
+
+impl<const N: usize> Sub for PointN<N> {
+    type Output = Self;
+
+    fn sub(mut self, other: Self) -> Self {
+        self -= other;
+        self
+    }
+}
+
+impl<const N: usize> SubAssign for PointN<N> {
+    fn sub_assign(&mut self, other: Self) {
+        for (c, o) in self.0.iter_mut().zip(other.0.iter()) {
+            *c -= o;
+        }
+    }
+}
+
+impl<const N: usize> Mul<i64> for PointN<N> {
+    type Output = Self;
+
+    fn mul(mut self, k: i64) -> Self {
+        for c in self.0.iter_mut() {
+            *c *= k;
+        }
+        self
+    }
+}
+
+impl<const N: usize> Neg for PointN<N> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self * -1
+    }
+}
+
+impl<const N: usize> fmt::Display for PointN<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "(")?;
+        for (i, c) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", c)?;
+        }
+        write!(f, ")")
+    }
+}
+
+/// A change of heading.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Turn {
+    Left,
+    Right,
+    Around,
+}
+
+impl Turn {
+    /// Counterclockwise quarter turns.
+    pub fn quarters(self) -> i64 {
+        match self {
+            Turn::Left => 1,
+            Turn::Right => -1,
+            Turn::Around => 2,
+        }
+    }
+}
+
+/// A compass direction.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    /// Clockwise from north.
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// The unit vector pointing in this direction.
+    pub fn vector(self) -> Point2 {
+        match self {
+            Direction::North => Point2::new(0, 1),
+            Direction::East => Point2::new(1, 0),
+            Direction::South => Point2::new(0, -1),
+            Direction::West => Point2::new(-1, 0),
+        }
+    }
+
+    /// The direction `quarters` quarter turns away, counterclockwise when positive.
+    pub fn rotate(self, quarters: i64) -> Self {
+        let idx = Direction::ALL.iter().position(|&d| d == self).unwrap() as i64;
+
+        Direction::ALL[(idx - quarters).rem_euclid(4) as usize]
+    }
+
+    pub fn turn(self, turn: Turn) -> Self {
+        self.rotate(turn.quarters())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::point::{Direction, Point2, Point3, PointN, Turn};
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Point2::new(1, -2);
+        let b = Point2::new(-4, 6);
+
+        assert_eq!(Point2::new(-3, 4), a + b);
+        assert_eq!(Point2::new(5, -8), a - b);
+        assert_eq!(Point2::new(3, -6), a * 3);
+        assert_eq!(Point2::new(-1, 2), -a);
+        assert_eq!(13, a.manhattan(b));
+        assert_eq!(6, Point3::new(1, -2, 3).norm1());
+        assert_eq!("(1, -2)", a.to_string());
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let p = PointN([5, 5, 5, 5]);
+        let neighbours = p.neighbours().collect::<HashSet<PointN<4>>>();
+
+        assert_eq!(80, neighbours.len());
+        assert!(!neighbours.contains(&p));
+        assert!(neighbours
+            .iter()
+            .all(|n| (*n - p).0.iter().all(|c| c.abs() <= 1)));
+        assert_eq!(6, Point3::ORIGIN.orthogonal_neighbours().count());
+        assert_eq!(
+            vec![
+                Point2::new(-1, 0),
+                Point2::new(1, 0),
+                Point2::new(0, -1),
+                Point2::new(0, 1)
+            ],
+            Point2::ORIGIN.orthogonal_neighbours().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_rotations() {
+        let p = Point2::new(10, 4);
+
+        assert_eq!(Point2::new(-4, 10), p.turn(Turn::Left));
+        assert_eq!(Point2::new(4, -10), p.turn(Turn::Right));
+        assert_eq!(-p, p.turn(Turn::Around));
+        assert_eq!(p.rotate(-1), p.rotate(3));
+        assert_eq!(p, p.rotate(8));
+
+        for &d in Direction::ALL.iter() {
+            assert_eq!(d.turn(Turn::Left).vector(), d.vector().turn(Turn::Left));
+            assert_eq!(d, d.turn(Turn::Right).turn(Turn::Left));
+        }
+        assert_eq!(Direction::West, Direction::North.turn(Turn::Left));
+    }
+}